@@ -0,0 +1,32 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use flcd::finite_automata::parser::parse_fa;
+
+/// Identifiers and integer constants of various lengths, like the ones the scanner sees.
+fn tokens() -> Vec<String> {
+    (0..1000)
+        .map(|idx| match idx % 4 {
+            0 => format!("identifier_{}", "x".repeat(idx % 40)),
+            1 => format!("-{}", idx * 7919),
+            2 => "a".repeat(idx % 64 + 1),
+            _ => format!("{}abc", idx)
+        })
+        .collect()
+}
+
+fn bench_matching(c: &mut Criterion) {
+    let tokens = tokens();
+    for file_name in ["id.in", "int.in"] {
+        let fa = parse_fa(file_name).unwrap().minimize().0;
+        let compiled = fa.compile().unwrap();
+
+        c.bench_function(&format!("{} check_match", file_name), |b| b.iter(|| {
+            tokens.iter().filter(|token| fa.check_match(black_box(token)).unwrap()).count()
+        }));
+        c.bench_function(&format!("{} compiled is_match", file_name), |b| b.iter(|| {
+            tokens.iter().filter(|token| compiled.is_match(black_box(token))).count()
+        }));
+    }
+}
+
+criterion_group!(benches, bench_matching);
+criterion_main!(benches);
@@ -0,0 +1,274 @@
+use std::collections::VecDeque;
+use crate::finite_automata::fa::{FiniteAutomaton, StateId};
+
+/// Lists the words accepted by an automaton in length-lexicographic order: shorter words first,
+/// words of the same length in alphabetical order. Built by `FiniteAutomaton::accepted_words`.
+///
+/// Words of each length are found by a depth-first search over a trimmed DFA that only follows
+/// transitions from which a final state can be reached in exactly the remaining number of steps,
+/// so every branch it enters produces a word.
+#[derive(Debug, Clone)]
+pub struct AcceptedWords {
+    outgoing: Vec<Vec<(char, usize)>>,
+    start: usize,
+    /// `can_finish[n][state]` is whether a final state is exactly `n` transitions away.
+    can_finish: Vec<Vec<bool>>,
+    /// Words are never longer than this; `None` for infinite languages.
+    max_length: Option<usize>,
+    length: usize,
+    stack: Vec<(usize, usize)>,
+    word: String
+}
+
+impl AcceptedWords {
+    fn new(fa: &FiniteAutomaton) -> Self {
+        let (dfa, _) = fa.as_dfa().trim();
+        let outgoing: Vec<Vec<(char, usize)>> = dfa.nodes.iter()
+            .map(|node| {
+                let mut edges: Vec<(char, usize)> = node.transitions.iter()
+                    .flat_map(|edge| edge.symbols.iter().map(move |symbol| (symbol, edge.destination.0)))
+                    .collect();
+                edges.sort();
+                edges
+            })
+            .collect();
+
+        // A trimmed DFA without cycles has no path visiting a state twice.
+        let max_length = if dfa.is_finite() { Some(dfa.nodes.len() - 1) } else { None };
+
+        AcceptedWords {
+            outgoing,
+            start: dfa.start.0,
+            can_finish: vec![dfa.nodes.iter().map(|node| node.is_final_state).collect()],
+            max_length,
+            length: 0,
+            stack: Vec::new(),
+            word: String::new()
+        }
+    }
+
+    fn extend_can_finish(&mut self, length: usize) {
+        while self.can_finish.len() <= length {
+            let last = self.can_finish.last().unwrap();
+            let next = self.outgoing.iter()
+                .map(|edges| edges.iter().any(|(_, destination)| last[*destination]))
+                .collect();
+            self.can_finish.push(next);
+        }
+    }
+}
+
+impl Iterator for AcceptedWords {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        loop {
+            if self.stack.is_empty() {
+                if self.max_length.is_some_and(|max_length| self.length > max_length) {
+                    return None;
+                }
+
+                self.extend_can_finish(self.length);
+                if self.can_finish[self.length][self.start] {
+                    self.stack.push((self.start, 0));
+                }
+                self.length += 1;
+                continue;
+            }
+
+            // The word being built has one symbol less than the stack has entries.
+            let depth = self.stack.len() - 1;
+            let target = self.length - 1;
+            if depth == target {
+                let res = self.word.clone();
+                self.stack.pop();
+                self.word.pop();
+                return Some(res);
+            }
+
+            let (state, edge_idx) = *self.stack.last().unwrap();
+            match self.outgoing[state].get(edge_idx) {
+                Some(&(symbol, destination)) => {
+                    self.stack.last_mut().unwrap().1 += 1;
+                    if self.can_finish[target - depth - 1][destination] {
+                        self.stack.push((destination, 0));
+                        self.word.push(symbol);
+                    }
+                },
+                None => {
+                    self.stack.pop();
+                    self.word.pop();
+                }
+            }
+        }
+    }
+}
+
+impl FiniteAutomaton {
+    /// `self` if it is already deterministic, the result of the subset construction otherwise.
+    pub(crate) fn as_dfa(&self) -> FiniteAutomaton {
+        if self.is_dfa() { self.clone() } else { self.to_dfa() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shortest_accepted().is_none()
+    }
+
+    /// Whether the language is finite, i.e. no cycle lies on a path from the start state to a
+    /// final state.
+    pub fn is_finite(&self) -> bool {
+        // Trimming leaves only such paths: a dead start state is kept, but without transitions.
+        let (dfa, _) = self.as_dfa().trim();
+
+        // Iterative depth-first search; a state still on the stack reached again closes a cycle.
+        let mut on_stack = vec![false; dfa.nodes.len()];
+        let mut done = vec![false; dfa.nodes.len()];
+        let mut stack = vec![(dfa.start.0, 0)];
+        on_stack[dfa.start.0] = true;
+        while let Some((state, edge_idx)) = stack.pop() {
+            match dfa.nodes[state].transitions.get(edge_idx) {
+                Some(edge) => {
+                    stack.push((state, edge_idx + 1));
+                    let next = edge.destination.0;
+                    if on_stack[next] {
+                        return false;
+                    }
+                    if !done[next] {
+                        on_stack[next] = true;
+                        stack.push((next, 0));
+                    }
+                },
+                None => {
+                    on_stack[state] = false;
+                    done[state] = true;
+                }
+            }
+        }
+
+        true
+    }
+
+    /// The shortest accepted word and, among those, the first in alphabetical order.
+    pub fn shortest_accepted(&self) -> Option<String> {
+        let dfa = self.as_dfa();
+        let mut words: Vec<Option<String>> = vec![None; dfa.nodes.len()];
+        words[dfa.start.0] = Some(String::new());
+        let mut queue = VecDeque::from([dfa.start]);
+        while let Some(state) = queue.pop_front() {
+            let word = words[state.0].clone().unwrap();
+            if dfa.node(state).is_final_state {
+                return Some(word);
+            }
+
+            // The edges of a DFA have disjoint labels, so the smallest symbol of each edge
+            // orders them.
+            let mut edges: Vec<(char, usize)> = dfa.node(state).transitions.iter()
+                .map(|edge| (edge.symbols.ranges()[0].0, edge.destination.0))
+                .collect();
+            edges.sort();
+            for (symbol, destination) in edges {
+                if words[destination].is_none() {
+                    let mut next = word.clone();
+                    next.push(symbol);
+                    words[destination] = Some(next);
+                    queue.push_back(StateId(destination));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// The number of accepted words of length `length`, saturating at `u128::MAX`.
+    pub fn count_words_of_length(&self, length: usize) -> u128 {
+        let dfa = self.as_dfa();
+        let mut counts = vec![0u128; dfa.nodes.len()];
+        counts[dfa.start.0] = 1;
+        for _ in 0..length {
+            let mut next = vec![0u128; dfa.nodes.len()];
+            for (state, node) in dfa.nodes.iter().enumerate() {
+                for edge in &node.transitions {
+                    let words = counts[state].saturating_mul(edge.symbols.len() as u128);
+                    next[edge.destination.0] = next[edge.destination.0].saturating_add(words);
+                }
+            }
+            counts = next;
+        }
+
+        dfa.nodes.iter()
+            .zip(counts)
+            .filter(|(node, _)| node.is_final_state)
+            .fold(0u128, |res, (_, count)| res.saturating_add(count))
+    }
+
+    /// The accepted words in length-lexicographic order. The iterator ends only if the language
+    /// is finite.
+    pub fn accepted_words(&self) -> AcceptedWords {
+        AcceptedWords::new(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use super::*;
+
+    fn regex(pattern: &str) -> FiniteAutomaton {
+        FiniteAutomaton::from_regex(pattern).unwrap()
+    }
+
+    #[test]
+    fn test_emptiness_and_finiteness() {
+        assert!(regex("∅").is_empty());
+        assert!(!regex("()").is_empty());
+        assert!(regex("ab|c?").is_finite());
+        assert!(!regex("ab*").is_finite());
+        assert!(regex("∅").is_finite());
+
+        // The cycle on q cannot lead to a final state, so it does not make the language infinite.
+        let fa = FiniteAutomaton::new(vec!["p", "q", "r"], "p", HashSet::from(["r"]), HashSet::from(['a', 'b']),
+                                      vec![("p", "q", 'a'), ("q", "q", 'a'), ("p", "r", 'b')]);
+        assert!(fa.is_finite());
+    }
+
+    #[test]
+    fn test_shortest_accepted() {
+        assert_eq!(regex("b+|aaa|ca").shortest_accepted(), Some("b".to_owned()));
+        assert_eq!(regex("ba|ab|ca").shortest_accepted(), Some("ab".to_owned()));
+        assert_eq!(regex("a*").shortest_accepted(), Some(String::new()));
+        assert_eq!(regex("∅").shortest_accepted(), None);
+    }
+
+    #[test]
+    fn test_count_words_of_length() {
+        let identifiers = regex("[A-Z_a-z][0-9A-Z_a-z]*");
+        assert_eq!(identifiers.count_words_of_length(0), 0);
+        assert_eq!(identifiers.count_words_of_length(1), 53);
+        assert_eq!(identifiers.count_words_of_length(3), 53 * 63 * 63);
+        assert_eq!(regex("[ab]*").count_words_of_length(200), u128::MAX);
+    }
+
+    #[test]
+    fn test_accepted_words() {
+        let words: Vec<String> = regex("b|a(a|b)|()").accepted_words().collect();
+        assert_eq!(words, ["", "b", "aa", "ab"]);
+
+        let words: Vec<String> = regex("(ab|c)*").accepted_words().take(7).collect();
+        assert_eq!(words, ["", "c", "ab", "cc", "abc", "cab", "ccc"]);
+
+        assert_eq!(regex("∅").accepted_words().next(), None);
+    }
+
+    #[test]
+    fn test_empty_language_with_cycles() {
+        let nothing = regex("[ab]*").complement();
+        assert!(nothing.is_empty());
+        assert!(nothing.is_finite());
+        assert_eq!(nothing.accepted_words().next(), None);
+
+        let looping = FiniteAutomaton::new(vec!["p"], "p", HashSet::new(), HashSet::from(['a']), vec![("p", "p", 'a')]);
+        assert!(looping.is_empty());
+        assert!(looping.is_finite());
+        assert_eq!(looping.accepted_words().next(), None);
+    }
+}
@@ -0,0 +1,179 @@
+use std::collections::{HashMap, VecDeque};
+use crate::finite_automata::fa::{FiniteAutomaton, NotDFAError, StateId};
+use crate::finite_automata::symbol_set::SymbolSet;
+
+/// The transitions leaving `state`, one per destination, in the order of their smallest symbol.
+/// The labels of a DFA's edges are disjoint, so this order does not depend on how the edges
+/// were written down.
+fn sorted_edges(fa: &FiniteAutomaton, state: StateId) -> Vec<(SymbolSet, StateId)> {
+    let mut res: Vec<(SymbolSet, StateId)> = Vec::new();
+    for edge in &fa.node(state).transitions {
+        match res.iter_mut().find(|(_, destination)| *destination == edge.destination) {
+            Some((symbols, _)) => *symbols = symbols.union(&edge.symbols),
+            None => res.push((edge.symbols.clone(), edge.destination))
+        }
+    }
+
+    res.sort_by_key(|(symbols, _)| symbols.ranges()[0].0);
+    res
+}
+
+impl FiniteAutomaton {
+    /// Renames the states reachable from the start state to `q0`, `q1`, ... in breadth-first
+    /// order, following the edges of each state in the order of their smallest symbol. The
+    /// unreachable states are dropped. Two minimal DFAs over the same alphabet accept the same
+    /// language exactly when their canonical forms are equal.
+    pub fn canonical(&self) -> Result<FiniteAutomaton, NotDFAError> {
+        if !self.is_dfa() {
+            return Err(NotDFAError);
+        }
+
+        let mut names: Vec<Option<String>> = vec![None; self.nodes.len()];
+        let mut order = Vec::new();
+        let mut queue = VecDeque::from([self.start]);
+        names[self.start.0] = Some("q0".to_owned());
+        while let Some(state) = queue.pop_front() {
+            order.push(state);
+            for (_, destination) in sorted_edges(self, state) {
+                if names[destination.0].is_none() {
+                    names[destination.0] = Some(format!("q{}", order.len() + queue.len()));
+                    queue.push_back(destination);
+                }
+            }
+        }
+
+        let name = |state: StateId| names[state.0].clone().unwrap();
+        let states = order.iter().map(|state| name(*state)).collect();
+        let final_states = order.iter()
+            .filter(|state| self.node(**state).is_final_state)
+            .map(|state| name(*state))
+            .collect();
+        let transitions = order.iter()
+            .flat_map(|state| sorted_edges(self, *state).into_iter().map(move |edge| (*state, edge)))
+            .map(|(state, (symbols, destination))| (name(state), name(destination), symbols))
+            .collect();
+
+        Ok(FiniteAutomaton::with_symbol_sets(states, "q0".to_owned(), final_states, self.alphabet.clone(), transitions))
+    }
+
+    /// Whether the DFAs `self` and `other` have the same structure up to the names of their
+    /// states: the same alphabet, and a one-to-one mapping of the states reachable from the start
+    /// states that keeps the start state, the final states and the transitions. Returns the
+    /// mapping from the state names of `self` to those of `other`, or `None` if there is none.
+    pub fn is_isomorphic(&self, other: &FiniteAutomaton) -> Result<Option<HashMap<String, String>>, NotDFAError> {
+        if !self.is_dfa() || !other.is_dfa() {
+            return Err(NotDFAError);
+        }
+        if self.alphabet != other.alphabet {
+            return Ok(None);
+        }
+
+        let mut mapping: Vec<Option<StateId>> = vec![None; self.nodes.len()];
+        let mut inverse: Vec<Option<StateId>> = vec![None; other.nodes.len()];
+        mapping[self.start.0] = Some(other.start);
+        inverse[other.start.0] = Some(self.start);
+        let mut queue = VecDeque::from([(self.start, other.start)]);
+        while let Some((state, image)) = queue.pop_front() {
+            if self.node(state).is_final_state != other.node(image).is_final_state {
+                return Ok(None);
+            }
+
+            let edges = sorted_edges(self, state);
+            let image_edges = sorted_edges(other, image);
+            if edges.len() != image_edges.len() {
+                return Ok(None);
+            }
+
+            for ((symbols, destination), (image_symbols, image_destination)) in edges.into_iter().zip(image_edges) {
+                if symbols != image_symbols {
+                    return Ok(None);
+                }
+
+                match (mapping[destination.0], inverse[image_destination.0]) {
+                    (None, None) => {
+                        mapping[destination.0] = Some(image_destination);
+                        inverse[image_destination.0] = Some(destination);
+                        queue.push_back((destination, image_destination));
+                    },
+                    (Some(mapped), Some(_)) if mapped == image_destination => {},
+                    _ => return Ok(None)
+                }
+            }
+        }
+
+        let res = self.state_ids()
+            .filter_map(|state| mapping[state.0].map(|image| (state, image)))
+            .map(|(state, image)| (self.node(state).id.clone(), other.node(image).id.clone()))
+            .collect();
+        Ok(Some(res))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use super::*;
+
+    // Binary numbers without leading zeros that are divisible by 3, written down twice with
+    // different state names and orders.
+    fn before_each() -> (FiniteAutomaton, FiniteAutomaton) {
+        let fa = FiniteAutomaton::new(
+            vec!["s", "r0", "r1", "r2", "z"],
+            "s",
+            HashSet::from(["r0", "z"]),
+            HashSet::from(['0', '1']),
+            vec![("s", "z", '0'), ("s", "r1", '1'), ("r0", "r0", '0'), ("r0", "r1", '1'),
+                 ("r1", "r2", '0'), ("r1", "r0", '1'), ("r2", "r1", '0'), ("r2", "r2", '1')]
+        );
+        let renamed = FiniteAutomaton::new(
+            vec!["two", "one", "zero", "start", "leading"],
+            "start",
+            HashSet::from(["zero", "leading"]),
+            HashSet::from(['0', '1']),
+            vec![("two", "two", '1'), ("two", "one", '0'), ("one", "zero", '1'), ("one", "two", '0'),
+                 ("zero", "zero", '0'), ("zero", "one", '1'), ("start", "one", '1'), ("start", "leading", '0')]
+        );
+
+        (fa, renamed)
+    }
+
+    #[test]
+    fn test_canonical() {
+        let (fa, renamed) = before_each();
+        let canonical = fa.canonical().unwrap();
+        assert_eq!(canonical, renamed.canonical().unwrap());
+        assert_eq!(canonical.nodes.iter().map(|node| node.id.as_str()).collect::<Vec<_>>(), ["q0", "q1", "q2", "q3", "q4"]);
+        assert!(canonical.check_match("1001").unwrap() && !canonical.check_match("111").unwrap());
+
+        let first = FiniteAutomaton::from_regex("(a|b)*abb").unwrap().minimize().0;
+        let second = FiniteAutomaton::from_regex("(a*b)*a(b|a)*abb|(b|a)*abb").unwrap().minimize().0;
+        assert_eq!(first.canonical().unwrap(), second.canonical().unwrap());
+
+        assert!(FiniteAutomaton::from_regex("ab|c").unwrap().canonical().is_err());
+    }
+
+    #[test]
+    fn test_is_isomorphic() {
+        let (fa, renamed) = before_each();
+        let mapping = fa.is_isomorphic(&renamed).unwrap().unwrap();
+        let expected = [("s", "start"), ("z", "leading"), ("r0", "zero"), ("r1", "one"), ("r2", "two")];
+        assert_eq!(mapping, expected.iter().map(|(state, image)| (state.to_string(), image.to_string())).collect());
+
+        let merged = FiniteAutomaton::new(vec!["p", "q"], "p", HashSet::from(["q"]), HashSet::from(['a', 'b']),
+                                          vec![("p", "q", 'a'), ("p", "q", 'b')]);
+        let split = FiniteAutomaton::with_symbol_sets(vec!["x", "y"], "x", HashSet::from(["y"]), HashSet::from(['a', 'b']),
+                                                      vec![("x", "y", SymbolSet::range('a', 'b'))]);
+        assert!(merged.is_isomorphic(&split).unwrap().is_some());
+
+        let mut other = fa.clone();
+        let r0 = other.find_state("r0").unwrap();
+        other.nodes[r0.0].is_final_state = false;
+        assert_eq!(fa.is_isomorphic(&other).unwrap(), None);
+
+        // Same language as `merged`, but one more state.
+        let larger = FiniteAutomaton::new(vec!["p", "q", "r"], "p", HashSet::from(["q", "r"]), HashSet::from(['a', 'b']),
+                                          vec![("p", "q", 'a'), ("p", "r", 'b')]);
+        assert_eq!(merged.is_isomorphic(&larger).unwrap(), None);
+        assert!(merged.is_isomorphic(&larger.minimize().0).unwrap().is_some());
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use crate::finite_automata::fa::{FiniteAutomaton, NotDFAError};
+
+/// Class of the characters outside the alphabet. They always lead to the dead state.
+const OTHER_CLASS: usize = 0;
+
+/// A DFA compiled into a dense transition table, indexed by state and by symbol class. Symbols
+/// that behave the same way in every state share a class, which keeps the table small for
+/// automata like `id.in` where most letters are interchangeable.
+///
+/// Missing transitions go to an explicit dead state, so matching is one class lookup and one
+/// table lookup per character, with no branching on the shape of the automaton.
+#[derive(Debug, Clone)]
+pub struct CompiledDfa {
+    ascii_classes: [usize; 128],
+    /// Sorted, disjoint ranges of non-ASCII symbols and their classes.
+    classes: Vec<(char, char, usize)>,
+    class_count: usize,
+    table: Vec<usize>,
+    is_final: Vec<bool>,
+    start: usize,
+    dead: usize
+}
+
+impl CompiledDfa {
+    fn class_of(&self, ch: char) -> usize {
+        if ch.is_ascii() {
+            self.ascii_classes[ch as usize]
+        } else {
+            self.classes
+                .binary_search_by(|(start, end, _)| {
+                    if *end < ch {
+                        std::cmp::Ordering::Less
+                    } else if *start > ch {
+                        std::cmp::Ordering::Greater
+                    } else {
+                        std::cmp::Ordering::Equal
+                    }
+                })
+                .map_or(OTHER_CLASS, |idx| self.classes[idx].2)
+        }
+    }
+
+    pub fn is_match(&self, label: &str) -> bool {
+        let mut state = self.start;
+        for ch in label.chars() {
+            state = self.table[state * self.class_count + self.class_of(ch)];
+            if state == self.dead {
+                return false;
+            }
+        }
+
+        self.is_final[state]
+    }
+
+    /// The length in bytes of the longest prefix of `text[start..]` that is accepted, or `None`
+    /// if not even the empty prefix is or `start` is not a character boundary of `text`. Same as
+    /// `FiniteAutomaton::longest_match`.
+    pub fn longest_match(&self, text: &str, start: usize) -> Option<usize> {
+        let rest = text.get(start..)?;
+        let mut state = self.start;
+        let mut res = if self.is_final[state] { Some(0) } else { None };
+        for (offset, ch) in rest.char_indices() {
+            state = self.table[state * self.class_count + self.class_of(ch)];
+            if state == self.dead {
+                break;
+            }
+            if self.is_final[state] {
+                res = Some(offset + ch.len_utf8());
+            }
+        }
+
+        res
+    }
+
+    /// Number of symbol classes, not counting the class of the characters outside the alphabet.
+    pub fn class_count(&self) -> usize {
+        self.class_count - 1
+    }
+}
+
+impl FiniteAutomaton {
+    /// Compiles `self` into a `CompiledDfa` accepting the same words as `check_match`.
+    pub fn compile(&self) -> Result<CompiledDfa, NotDFAError> {
+        if !self.is_dfa() {
+            return Err(NotDFAError);
+        }
+
+        let dead = self.nodes.len();
+        let ranges = self.symbol_classes();
+
+        // The column of a range is the state it leads to from every state.
+        let mut columns: Vec<Vec<usize>> = vec![vec![dead; self.nodes.len()]; ranges.len()];
+        for (source, node) in self.nodes.iter().enumerate() {
+            for edge in &node.transitions {
+                for (range_idx, range) in ranges.iter().enumerate() {
+                    if edge.symbols.contains(range.ranges()[0].0) {
+                        columns[range_idx][source] = edge.destination.0;
+                    }
+                }
+            }
+        }
+
+        let mut class_columns: Vec<Vec<usize>> = vec![vec![dead; self.nodes.len()]];
+        let mut class_index: HashMap<&[usize], usize> = HashMap::new();
+        let mut ascii_classes = [OTHER_CLASS; 128];
+        let mut classes = Vec::new();
+        for (range, column) in ranges.iter().zip(&columns) {
+            let class = match class_index.get(column.as_slice()) {
+                Some(class) => *class,
+                None => {
+                    class_columns.push(column.clone());
+                    class_index.insert(column, class_columns.len() - 1);
+                    class_columns.len() - 1
+                }
+            };
+
+            let (start, end) = range.ranges()[0];
+            for ch in start..=end.min('\x7F') {
+                ascii_classes[ch as usize] = class;
+            }
+            if !end.is_ascii() {
+                classes.push((start.max('\u{80}'), end, class));
+            }
+        }
+
+        let class_count = class_columns.len();
+        let mut table = vec![dead; (self.nodes.len() + 1) * class_count];
+        for (class, column) in class_columns.iter().enumerate() {
+            for (state, next) in column.iter().enumerate() {
+                table[state * class_count + class] = *next;
+            }
+        }
+
+        let is_final = self.nodes.iter().map(|node| node.is_final_state).chain([false]).collect();
+
+        Ok(CompiledDfa {
+            ascii_classes,
+            classes,
+            class_count,
+            table,
+            is_final,
+            start: self.start.0,
+            dead
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use super::*;
+    use crate::finite_automata::fa::EPSILON;
+    use crate::finite_automata::parser::parse_fa;
+
+    fn words(alphabet: &[char], max_len: usize) -> Vec<String> {
+        let mut res = vec![String::new()];
+        let mut last = vec![String::new()];
+        for _ in 0..max_len {
+            last = last.iter()
+                .flat_map(|word| alphabet.iter().map(move |ch| format!("{}{}", word, ch)))
+                .collect();
+            res.extend(last.iter().cloned());
+        }
+
+        res
+    }
+
+    #[test]
+    fn test_compile() {
+        let int_const = parse_fa("int.in").unwrap();
+        let compiled = int_const.compile().unwrap();
+        assert_eq!(compiled.class_count(), 3);
+
+        for word in words(&['0', '1', '9', '+', '-', 'a', 'ă'], 4) {
+            assert_eq!(compiled.is_match(&word), int_const.check_match(&word).unwrap(), "{}", word);
+        }
+    }
+
+    #[test]
+    fn test_longest_match() {
+        let fa = FiniteAutomaton::new(vec!["p", "q", "r"], "p", HashSet::from(["p", "r"]), HashSet::from(['a', 'b']),
+                                      vec![("p", "q", 'a'), ("q", "r", 'b'), ("r", "q", 'a')]);
+        let compiled = fa.compile().unwrap();
+        for word in words(&['a', 'b', 'ă'], 5) {
+            for (start, _) in word.char_indices() {
+                assert_eq!(compiled.longest_match(&word, start), fa.longest_match(&word, start), "{} {}", word, start);
+            }
+        }
+
+        // Offsets inside a character or past the end are not matched.
+        assert_eq!(compiled.longest_match("ă", 1), None);
+        assert_eq!(compiled.longest_match("ab", 3), None);
+    }
+
+    #[test]
+    fn test_compile_nfa() {
+        let fa = FiniteAutomaton::new(vec!["p", "q"], "p", HashSet::from(["q"]), HashSet::from(['a']),
+                                      vec![("p", "q", EPSILON)]);
+        assert!(fa.compile().is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashSet;
+use crate::finite_automata::fa::FiniteAutomaton;
+use crate::finite_automata::symbol_set::SymbolSet;
+
+impl FiniteAutomaton {
+    /// Whether every state has a transition on every symbol of the alphabet.
+    pub fn is_complete(&self) -> bool {
+        let alphabet: SymbolSet = self.alphabet.iter().cloned().collect();
+        self.nodes.iter().all(|node| {
+            let covered = node.transitions.iter().fold(SymbolSet::new(), |res, edge| res.union(&edge.symbols));
+            alphabet.is_subset(&covered)
+        })
+    }
+
+    /// Builds an equivalent complete DFA: missing transitions go to a new non-final sink state
+    /// named `∅` (with `'` appended if that name is taken) that loops on every symbol. Automata
+    /// that are not deterministic are determinized first, and complete DFAs are returned as
+    /// they are.
+    pub fn complete(&self) -> FiniteAutomaton {
+        if !self.is_dfa() {
+            return self.to_dfa().complete();
+        }
+        if self.is_complete() {
+            return self.clone();
+        }
+
+        let mut sink = "∅".to_owned();
+        while self.nodes.iter().any(|node| node.id == sink) {
+            sink.push('\'');
+        }
+
+        let alphabet: SymbolSet = self.alphabet.iter().cloned().collect();
+        let mut transitions = self.symbol_set_transitions();
+        for node in &self.nodes {
+            let covered = node.transitions.iter().fold(SymbolSet::new(), |res, edge| res.union(&edge.symbols));
+            let missing = alphabet.difference(&covered);
+            if !missing.is_empty() {
+                transitions.push((node.id.clone(), sink.clone(), missing));
+            }
+        }
+        transitions.push((sink.clone(), sink.clone(), alphabet));
+
+        let states = self.nodes.iter().map(|node| node.id.clone()).chain([sink]).collect();
+        let final_states = self.nodes.iter().filter(|node| node.is_final_state).map(|node| node.id.clone()).collect();
+        FiniteAutomaton::with_symbol_sets(states, self.node(self.start).id.clone(), final_states, self.alphabet.clone(), transitions)
+    }
+
+    /// Builds a DFA accepting exactly the words over the alphabet that `self` rejects.
+    pub fn complement(&self) -> FiniteAutomaton {
+        let complete = self.complete();
+        let states = complete.nodes.iter().map(|node| node.id.as_str()).collect();
+        let final_states: HashSet<&str> = complete.nodes.iter()
+            .filter(|node| !node.is_final_state)
+            .map(|node| node.id.as_str())
+            .collect();
+        let transitions = complete.symbol_set_transitions();
+        let transitions = transitions.iter()
+            .map(|(source, destination, symbols)| (source.as_str(), destination.as_str(), symbols.clone()))
+            .collect();
+
+        FiniteAutomaton::with_symbol_sets(states,
+                                          complete.node(complete.start).id.as_str(),
+                                          final_states,
+                                          complete.alphabet.clone(),
+                                          transitions)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::finite_automata::equivalence::Equivalence;
+
+    // Words over {0, 1} of the form 10*10*.
+    fn before_each() -> FiniteAutomaton {
+        FiniteAutomaton::new(
+            vec!["p", "q", "r"],
+            "p",
+            HashSet::from(["r"]),
+            HashSet::from(['0', '1']),
+            vec![("p", "q", '1'), ("q", "q", '0'), ("q", "r", '1'), ("r", "r", '0')]
+        )
+    }
+
+    #[test]
+    fn test_complete() {
+        let fa = before_each();
+        assert!(!fa.is_complete());
+
+        let complete = fa.complete();
+        assert!(complete.is_complete() && complete.is_dfa());
+        assert_eq!(complete.nodes.len(), 4);
+        assert_eq!(complete.nodes[3].id, "∅");
+        assert_eq!(complete.equivalent(&fa), Equivalence::Equivalent);
+        for label in ["0", "111", "1010", "10100"] {
+            assert_eq!(complete.check_match(label).unwrap(), fa.check_match(label).unwrap());
+        }
+
+        assert_eq!(complete.complete(), complete);
+    }
+
+    #[test]
+    fn test_complement() {
+        let fa = before_each();
+        let complement = fa.complement();
+        for label in ["", "0", "1", "111", "1010", "10100", "0110"] {
+            assert_ne!(complement.check_match(label).unwrap(), fa.check_match(label).unwrap());
+        }
+
+        assert!(complement.intersection(&fa).minimize().0.nodes.iter().all(|node| !node.is_final_state));
+        assert_eq!(complement.complement().equivalent(&fa), Equivalence::Equivalent);
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use crate::finite_automata::fa::{group_transitions, FiniteAutomaton, StateId};
+use crate::finite_automata::symbol_set::SymbolSet;
+
+/// Names `set` after its members, appending `'` while the name is taken by an original state or
+/// another set, and marks the name as taken.
+fn composite_id(set: &BTreeSet<String>, used: &mut HashSet<String>) -> String {
+    if set.len() == 1 {
+        return set.iter().next().unwrap().clone();
+    }
+
+    let members: Vec<&str> = set.iter().map(String::as_str).collect();
+    let mut id = format!("{{{}}}", members.join("|"));
+    while used.contains(&id) {
+        id.push('\'');
+    }
+
+    used.insert(id.clone());
+    id
+}
+
+impl FiniteAutomaton {
+    /// Builds an equivalent DFA using the subset construction, following epsilon
+    /// transitions through epsilon closures. Every state of the result stands for a
+    /// set of states of `self`; singleton sets keep the id of their only state, the
+    /// others are named after their members, e.g. `{p|q}`, with `'` appended when that
+    /// name is already taken. The empty set is not added, so the result may be partial.
+    pub fn to_dfa(&self) -> FiniteAutomaton {
+        let composite = self.to_dfa_with_states();
+        let states: Vec<String> = composite.states.iter().map(|(id, _)| id.clone()).collect();
+        FiniteAutomaton::with_symbol_sets(states,
+                                          composite.start,
+                                          composite.final_states,
+                                          self.alphabet.clone(),
+                                          group_transitions(composite.transitions))
+    }
+
+    /// Same as `to_dfa`, but also returns which original states each new state is made of.
+    pub fn to_dfa_with_states(&self) -> CompositeAutomaton {
+        // Symbols of the same class lead to the same states, so one of each is enough.
+        let classes = self.symbol_classes();
+
+        let mut used: HashSet<String> = self.nodes.iter().map(|node| node.id.clone()).collect();
+        let names = |set: &BTreeSet<StateId>| -> BTreeSet<String> {
+            set.iter().map(|state| self.node(*state).id.clone()).collect()
+        };
+
+        let mut ids: HashMap<BTreeSet<StateId>, String> = HashMap::new();
+        let mut sets = Vec::new();
+        let mut transitions = Vec::new();
+        let mut queue = VecDeque::new();
+
+        let start_set = self.epsilon_closure(&BTreeSet::from([self.start]));
+        let start = composite_id(&names(&start_set), &mut used);
+        ids.insert(start_set.clone(), start.clone());
+        sets.push((start.clone(), start_set.clone()));
+        queue.push_back(start_set);
+
+        while let Some(set) = queue.pop_front() {
+            let source = ids[&set].clone();
+            for class in &classes {
+                let next = self.epsilon_closure(&self.step(&set, class.ranges()[0].0));
+                if next.is_empty() {
+                    continue;
+                }
+
+                let destination = match ids.get(&next) {
+                    Some(id) => id.clone(),
+                    None => {
+                        let id = composite_id(&names(&next), &mut used);
+                        ids.insert(next.clone(), id.clone());
+                        sets.push((id.clone(), next.clone()));
+                        queue.push_back(next);
+                        id
+                    }
+                };
+
+                transitions.push((source.clone(), destination, class.clone()));
+            }
+        }
+
+        let final_states = sets.iter()
+            .filter(|(_, set)| set.iter().any(|state| self.node(*state).is_final_state))
+            .map(|(id, _)| id.clone())
+            .collect();
+        let states = sets.iter().map(|(id, set)| (id.clone(), names(set))).collect();
+
+        CompositeAutomaton {
+            states,
+            start,
+            final_states,
+            transitions
+        }
+    }
+}
+
+/// The raw result of the subset construction, before it is turned into a `FiniteAutomaton`.
+#[derive(Debug)]
+pub struct CompositeAutomaton {
+    pub states: Vec<(String, BTreeSet<String>)>,
+    pub start: String,
+    pub final_states: HashSet<String>,
+    pub transitions: Vec<(String, String, SymbolSet)>
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::finite_automata::fa::EPSILON;
+
+    fn before_each() -> FiniteAutomaton {
+        // Words over {a, b} ending in "ab".
+        FiniteAutomaton::new(
+            vec!["p", "q", "r"],
+            "p",
+            HashSet::from(["r"]),
+            HashSet::from(['a', 'b']),
+            vec![("p", "p", 'a'), ("p", "p", 'b'), ("p", "q", 'a'), ("q", "r", 'b')]
+        )
+    }
+
+    #[test]
+    fn test_to_dfa() {
+        let fa = before_each();
+        assert!(!fa.is_dfa());
+
+        let dfa = fa.to_dfa();
+        assert!(dfa.is_dfa());
+        assert_eq!(dfa.nodes.len(), 3);
+
+        for label in ["ab", "aab", "bab", "abab"] {
+            assert!(dfa.check_match(label).unwrap());
+        }
+        for label in ["", "a", "b", "aba", "abb"] {
+            assert!(!dfa.check_match(label).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_to_dfa_with_epsilon_transitions() {
+        // Thompson-style automaton for a*b.
+        let fa = FiniteAutomaton::new(
+            vec!["0", "1", "2", "3", "4"],
+            "0",
+            HashSet::from(["4"]),
+            HashSet::from(['a', 'b']),
+            vec![("0", "1", EPSILON), ("0", "3", EPSILON), ("1", "2", 'a'),
+                 ("2", "1", EPSILON), ("2", "3", EPSILON), ("3", "4", 'b')]
+        );
+        assert!(!fa.is_dfa());
+        let closure = fa.epsilon_closure(&BTreeSet::from([fa.start]));
+        let ids: BTreeSet<&str> = closure.iter().map(|state| fa.node(*state).id.as_str()).collect();
+        assert_eq!(ids, BTreeSet::from(["0", "1", "3"]));
+
+        let dfa = fa.to_dfa();
+        assert!(dfa.is_dfa());
+        for label in ["b", "ab", "aaab"] {
+            assert!(dfa.check_match(label).unwrap());
+        }
+        for label in ["", "a", "ba", "abb"] {
+            assert!(!dfa.check_match(label).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_composite_states() {
+        let composite = before_each().to_dfa_with_states();
+
+        let ids: Vec<String> = composite.states.into_iter().map(|(id, _)| id).collect();
+        assert_eq!(ids, vec!["p", "{p|q}", "{p|r}"]);
+    }
+
+    #[test]
+    fn test_composite_ids_are_unique() {
+        // {a|b, c} and {a, b|c} would both be named {a|b|c}.
+        let fa = FiniteAutomaton::new(
+            vec!["s", "a|b", "c", "a", "b|c"],
+            "s",
+            HashSet::from(["c"]),
+            HashSet::from(['x', 'y']),
+            vec![("s", "a|b", 'x'), ("s", "c", 'x'), ("s", "a", 'y'), ("s", "b|c", 'y'), ("a|b", "c", 'x')]
+        );
+        assert!(!fa.accepts("y"));
+
+        let dfa = fa.to_dfa();
+        let ids: Vec<&str> = dfa.nodes.iter().map(|node| node.id.as_str()).collect();
+        assert_eq!(ids, ["s", "{a|b|c}", "{a|b|c}'", "c"]);
+        assert!(dfa.check_match("x").unwrap() && dfa.check_match("xx").unwrap());
+        assert!(!dfa.check_match("y").unwrap());
+    }
+}
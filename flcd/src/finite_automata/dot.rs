@@ -0,0 +1,78 @@
+use crate::finite_automata::fa::FiniteAutomaton;
+use crate::finite_automata::symbol_set::SymbolSet;
+
+fn quote(id: &str) -> String {
+    format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+impl FiniteAutomaton {
+    /// Renders the automaton in Graphviz DOT. Final states are double circles, the start state
+    /// gets an arrow from an invisible node and parallel edges are merged into one label.
+    pub fn to_dot(&self) -> String {
+        let mut res = String::new();
+        res.push_str("digraph FA {\n");
+        res.push_str("    rankdir = LR;\n");
+        res.push_str("    \"\" [shape = none, label = \"\"];\n");
+
+        for node in &self.nodes {
+            let shape = if node.is_final_state { "doublecircle" } else { "circle" };
+            res.push_str(&format!("    {} [shape = {}];\n", quote(&node.id), shape));
+        }
+
+        res.push_str(&format!("    \"\" -> {};\n", quote(&self.node(self.start).id)));
+
+        let mut edges: Vec<(String, String, SymbolSet)> = Vec::new();
+        for (source, destination, symbols) in self.symbol_set_transitions() {
+            match edges.iter_mut().find(|(s, d, _)| *s == source && *d == destination) {
+                Some((_, _, existing)) => *existing = existing.union(&symbols),
+                None => edges.push((source, destination, symbols))
+            }
+        }
+
+        for (source, destination, symbols) in edges {
+            // Runs of three or more symbols are shown as ranges.
+            let label: Vec<String> = symbols.ranges()
+                .iter()
+                .flat_map(|(start, end)| match *end as u32 - *start as u32 {
+                    0 => vec![start.to_string()],
+                    1 => vec![start.to_string(), end.to_string()],
+                    _ => vec![format!("{}-{}", start, end)]
+                })
+                .collect();
+            res.push_str(&format!("    {} -> {} [label = {}];\n",
+                                  quote(&source),
+                                  quote(&destination),
+                                  quote(&label.join(", "))));
+        }
+
+        res.push_str("}\n");
+        res
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use super::*;
+
+    #[test]
+    fn test_to_dot() {
+        let fa = FiniteAutomaton::new(
+            vec!["p", "q"],
+            "p",
+            HashSet::from(["q"]),
+            HashSet::from(['0', '1']),
+            vec![("p", "q", '1'), ("p", "q", '0'), ("q", "q", '0')]
+        );
+
+        assert_eq!(fa.to_dot(), "digraph FA {\n\
+                                 \x20   rankdir = LR;\n\
+                                 \x20   \"\" [shape = none, label = \"\"];\n\
+                                 \x20   \"p\" [shape = circle];\n\
+                                 \x20   \"q\" [shape = doublecircle];\n\
+                                 \x20   \"\" -> \"p\";\n\
+                                 \x20   \"p\" -> \"q\" [label = \"0, 1\"];\n\
+                                 \x20   \"q\" -> \"q\" [label = \"0\"];\n\
+                                 }\n");
+    }
+}
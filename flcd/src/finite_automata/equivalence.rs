@@ -0,0 +1,62 @@
+use crate::finite_automata::fa::FiniteAutomaton;
+
+#[derive(Debug, PartialEq)]
+pub enum Equivalence {
+    Equivalent,
+    /// The shortest (and among those, the smallest) word accepted by exactly one of the automata.
+    Counterexample(String)
+}
+
+impl FiniteAutomaton {
+    pub fn equivalent(&self, other: &FiniteAutomaton) -> Equivalence {
+        match self.symmetric_difference(other).shortest_accepted() {
+            Some(word) => Equivalence::Counterexample(word),
+            None => Equivalence::Equivalent
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use super::*;
+
+    fn ends_in_ab() -> FiniteAutomaton {
+        FiniteAutomaton::new(
+            vec!["p", "q", "r"],
+            "p",
+            HashSet::from(["r"]),
+            HashSet::from(['a', 'b']),
+            vec![("p", "p", 'a'), ("p", "p", 'b'), ("p", "q", 'a'), ("q", "r", 'b')]
+        )
+    }
+
+    #[test]
+    fn test_equivalent() {
+        let fa = ends_in_ab();
+        let (minimal, _) = fa.minimize();
+        assert_eq!(fa.equivalent(&minimal), Equivalence::Equivalent);
+        assert_eq!(fa.equivalent(&fa.to_dfa()), Equivalence::Equivalent);
+    }
+
+    #[test]
+    fn test_counterexample() {
+        // Accepts "ab" and "bab" but not "aab".
+        let other = FiniteAutomaton::new(
+            vec!["p", "q", "r"],
+            "p",
+            HashSet::from(["r"]),
+            HashSet::from(['a', 'b']),
+            vec![("p", "p", 'b'), ("p", "q", 'a'), ("q", "r", 'b')]
+        );
+
+        assert_eq!(ends_in_ab().equivalent(&other), Equivalence::Counterexample("aab".to_owned()));
+    }
+
+    #[test]
+    fn test_empty_counterexample() {
+        let fa = ends_in_ab();
+        let with_empty = fa.union(&FiniteAutomaton::new(vec!["s"], "s", HashSet::from(["s"]), HashSet::new(), Vec::new()));
+        assert_eq!(fa.equivalent(&with_empty), Equivalence::Counterexample(String::new()));
+    }
+}
@@ -1,117 +1,275 @@
-use std::cell::RefCell;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fmt::{Display, Formatter};
-use std::rc::Rc;
+use std::hash::Hash;
+use crate::finite_automata::symbol_set::SymbolSet;
 
-#[derive(Debug)]
+/// Label of a transition that consumes no input. It is never part of the alphabet.
+pub const EPSILON: char = 'ε';
+
+/// Index of a state in `FiniteAutomaton::nodes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct StateId(pub usize);
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Edge {
-    destination: Rc<RefCell<Node>>,
-    transition: char
+    pub destination: StateId,
+    pub symbols: SymbolSet
+}
+
+impl Edge {
+    /// Epsilon edges are labelled with the set holding only `EPSILON`.
+    pub fn is_epsilon(&self) -> bool {
+        self.symbols.contains(EPSILON)
+    }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Node {
-    pub id: char,
+    pub id: String,
     pub transitions: Vec<Edge>,
     pub is_final_state: bool
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct FiniteAutomaton {
-    pub nodes: Vec<Rc<RefCell<Node>>>,
-    pub start: Rc<RefCell<Node>>,
+    pub nodes: Vec<Node>,
+    pub start: StateId,
     pub alphabet: HashSet<char>
 }
 
 impl FiniteAutomaton {
-    pub fn new(states: Vec<char>,
-               start_state: char,
-               final_states: HashSet<char>,
-               alphabet: HashSet<char>,
-               transitions: Vec<(char, char, char)>) -> Self {
+    pub fn new<S: AsRef<str> + Eq + Hash>(states: Vec<S>,
+                                          start_state: S,
+                                          final_states: HashSet<S>,
+                                          alphabet: HashSet<char>,
+                                          transitions: Vec<(S, S, char)>) -> Self {
+        let transitions = transitions
+            .into_iter()
+            .map(|(source, destination, symbol)| (source, destination, SymbolSet::single(symbol)))
+            .collect();
+
+        FiniteAutomaton::with_symbol_sets(states, start_state, final_states, alphabet, transitions)
+    }
 
+    /// Same as `new`, but every transition is labelled with a set of symbols. Panics if the
+    /// alphabet contains `EPSILON`, which only ever labels epsilon transitions.
+    pub fn with_symbol_sets<S: AsRef<str> + Eq + Hash>(states: Vec<S>,
+                                                       start_state: S,
+                                                       final_states: HashSet<S>,
+                                                       alphabet: HashSet<char>,
+                                                       transitions: Vec<(S, S, SymbolSet)>) -> Self {
+        if alphabet.contains(&EPSILON) {
+            panic!("Epsilon cannot be part of the alphabet!");
+        }
+
+        let alphabet_set: SymbolSet = alphabet.iter().cloned().collect();
         let mut nodes = Vec::new();
         let mut start = Vec::new();
         let mut nodes_id_map = HashMap::new();
         for state in states {
             let node = Node {
-                id: state,
+                id: state.as_ref().to_owned(),
                 transitions: Vec::new(),
                 is_final_state: final_states.contains(&state)
             };
 
-            let node_wrapper = Rc::new(RefCell::new(node));
-            nodes.push(node_wrapper.clone());
-            nodes_id_map.insert(state, node_wrapper.clone());
+            let state_id = StateId(nodes.len());
+            nodes.push(node);
+            nodes_id_map.insert(state.as_ref().to_owned(), state_id);
 
             if start_state == state {
-                start.push(node_wrapper);
+                start.push(state_id);
             }
         }
 
         for transition in transitions {
-            let source = nodes_id_map.get(&transition.0).unwrap().clone();
-            let destination = nodes_id_map.get(&transition.1).unwrap().clone();
+            let source = *nodes_id_map.get(transition.0.as_ref()).unwrap();
+            let destination = *nodes_id_map.get(transition.1.as_ref()).unwrap();
 
-            if !alphabet.contains(&transition.2) {
+            if transition.2 != SymbolSet::single(EPSILON) && !transition.2.is_subset(&alphabet_set) {
                 panic!("Transition not in alphabet!");
             }
 
             let edge = Edge {
                 destination,
-                transition: transition.2,
+                symbols: transition.2,
             };
 
-            source.borrow_mut().transitions.push(edge);
+            nodes[source.0].transitions.push(edge);
         }
 
         FiniteAutomaton {
             nodes,
             alphabet,
-            start: start[0].clone()
+            start: start[0]
         }
     }
 
+    pub fn node(&self, state: StateId) -> &Node {
+        &self.nodes[state.0]
+    }
+
+    pub fn state_ids(&self) -> impl Iterator<Item = StateId> {
+        (0..self.nodes.len()).map(StateId)
+    }
+
+    pub fn find_state(&self, id: &str) -> Option<StateId> {
+        self.nodes.iter().position(|node| node.id == id).map(StateId)
+    }
+
     pub fn is_dfa(&self) -> bool {
         for node in &self.nodes {
-            let mut transition_set = HashSet::new();
-            for edge in &node.borrow().transitions {
+            if node.transitions.iter().any(Edge::is_epsilon) {
+                return false;
+            }
 
-                if transition_set.contains(&edge.transition) {
-                    return false;
+            // The labels of one edge never overlap each other, so after sorting all of them
+            // an overlap between two edges shows up between neighbours.
+            let mut ranges: Vec<(char, char)> = node.transitions
+                .iter()
+                .flat_map(|edge| edge.symbols.ranges().iter().cloned())
+                .collect();
+            ranges.sort();
+            if ranges.windows(2).any(|pair| pair[1].0 <= pair[0].1) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Every transition as a (source, destination, symbol) triple, with one triple per symbol
+    /// of each edge.
+    pub fn transitions(&self) -> Vec<(String, String, char)> {
+        let mut res = Vec::new();
+        for (source, destination, symbols) in self.symbol_set_transitions() {
+            for symbol in symbols.iter() {
+                res.push((source.clone(), destination.clone(), symbol));
+            }
+        }
+
+        res
+    }
+
+    /// Every edge as a (source, destination, symbols) triple.
+    pub fn symbol_set_transitions(&self) -> Vec<(String, String, SymbolSet)> {
+        let mut res = Vec::new();
+        for node in &self.nodes {
+            for edge in &node.transitions {
+                res.push((node.id.clone(), self.node(edge.destination).id.clone(), edge.symbols.clone()));
+            }
+        }
+
+        res
+    }
+
+    /// The symbols used on transitions, split into ranges that every transition either covers
+    /// completely or not at all, so all the symbols of a range behave the same way.
+    pub(crate) fn symbol_classes(&self) -> Vec<SymbolSet> {
+        SymbolSet::partition(self.nodes.iter()
+            .flat_map(|node| &node.transitions)
+            .filter(|edge| !edge.is_epsilon())
+            .map(|edge| &edge.symbols))
+    }
+
+    pub fn has_epsilon_transitions(&self) -> bool {
+        self.nodes.iter().any(|node| node.transitions.iter().any(Edge::is_epsilon))
+    }
+
+    /// The states reachable from `states` using only epsilon transitions, `states` included.
+    pub fn epsilon_closure(&self, states: &BTreeSet<StateId>) -> BTreeSet<StateId> {
+        let mut closure = states.clone();
+        let mut stack: Vec<StateId> = states.iter().cloned().collect();
+        while let Some(state) = stack.pop() {
+            for edge in &self.node(state).transitions {
+                if edge.is_epsilon() && closure.insert(edge.destination) {
+                    stack.push(edge.destination);
                 }
+            }
+        }
+
+        closure
+    }
+
+    /// The states reached from `states` by reading `symbol`, before taking epsilon closures.
+    /// Reading `EPSILON` itself leads nowhere, since it is never a symbol of the alphabet.
+    pub fn step(&self, states: &BTreeSet<StateId>, symbol: char) -> BTreeSet<StateId> {
+        states.iter()
+            .flat_map(|state| &self.node(*state).transitions)
+            .filter(|edge| !edge.is_epsilon() && edge.symbols.contains(symbol))
+            .map(|edge| edge.destination)
+            .collect()
+    }
 
-                transition_set.insert(edge.transition);
+    /// Decides whether `label` is accepted by following all runs at once, so unlike `check_match`
+    /// it also works for nondeterministic automata and automata with epsilon transitions.
+    pub fn accepts(&self, label: &str) -> bool {
+        let mut current = self.epsilon_closure(&BTreeSet::from([self.start]));
+        for ch in label.chars() {
+            let next = self.step(&current, ch);
+            if next.is_empty() {
+                return false;
             }
+
+            current = self.epsilon_closure(&next);
         }
 
-        true
+        current.iter().any(|state| self.node(*state).is_final_state)
+    }
+
+    /// The length in bytes of the longest prefix of `text[start..]` that is accepted, or `None`
+    /// if not even the empty prefix is. `start` is a byte offset; if it is past the end of `text`
+    /// or inside a character, the result is `None`. Like `accepts`, this works for
+    /// nondeterministic automata.
+    pub fn longest_match(&self, text: &str, start: usize) -> Option<usize> {
+        let rest = text.get(start..)?;
+        let is_final = |states: &BTreeSet<StateId>| states.iter().any(|state| self.node(*state).is_final_state);
+
+        let mut current = self.epsilon_closure(&BTreeSet::from([self.start]));
+        let mut res = if is_final(&current) { Some(0) } else { None };
+        for (offset, ch) in rest.char_indices() {
+            let next = self.step(&current, ch);
+            if next.is_empty() {
+                break;
+            }
+
+            current = self.epsilon_closure(&next);
+            if is_final(&current) {
+                res = Some(offset + ch.len_utf8());
+            }
+        }
+
+        res
     }
 
     pub fn check_match(&self, label: &str) -> Result<bool, NotDFAError> {
-        let mut node = self.start.clone();
+        let mut node = self.node(self.start);
         for ch in label.chars() {
             let mut matching = 0;
-            let mut next_node = Default::default();
-            for edge in &node.borrow().transitions {
-                if edge.transition == ch {
-                    matching += 1;
-                    next_node = edge.destination.clone();
+            let mut next_node = None;
+            for edge in &node.transitions {
+                if edge.is_epsilon() {
+                    return Err(NotDFAError);
                 }
-            }
 
-            if matching == 0 {
-                return Ok(false);
+                if edge.symbols.contains(ch) {
+                    matching += 1;
+                    next_node = Some(edge.destination);
+                }
             }
 
-            if matching > 1 {
-                return Err(NotDFAError)
+            match (matching, next_node) {
+                (1, Some(next)) => node = self.node(next),
+                (0, _) => return Ok(false),
+                _ => return Err(NotDFAError)
             }
+        }
 
-            node = next_node;
+        if node.transitions.iter().any(Edge::is_epsilon) {
+            return Err(NotDFAError);
         }
 
-        Ok(node.clone().borrow().is_final_state)
+        Ok(node.is_final_state)
     }
 }
 
@@ -120,36 +278,37 @@ impl Display for FiniteAutomaton {
         let mut res = String::new();
         res.push_str("States: ");
         for node in &self.nodes {
-            res.push(node.borrow().id);
-            res.push_str(" ");
+            res.push_str(&node.id);
+            res.push(' ');
         }
 
-        res.push_str("\n");
+        res.push('\n');
         res.push_str("Input state: ");
-        res.push(self.start.borrow().id);
+        res.push_str(&self.node(self.start).id);
 
         res.push_str("\nOutput states: ");
         for node in &self.nodes {
-            let borrow = node.borrow();
-            if borrow.is_final_state {
-                res.push(borrow.id);
-                res.push_str(" ");
+            if node.is_final_state {
+                res.push_str(&node.id);
+                res.push(' ');
             }
         }
 
         res.push_str("\nAlphabet: ");
-        for ch in &self.alphabet {
-            res.push(*ch);
-            res.push_str(" ");
+        let mut alphabet: Vec<char> = self.alphabet.iter().cloned().collect();
+        alphabet.sort();
+        for ch in alphabet {
+            res.push(ch);
+            res.push(' ');
         }
 
         res.push_str("\nTransitions:\n");
         for node in &self.nodes {
-            for edge in &node.borrow().transitions {
+            for edge in &node.transitions {
                 res.push_str(&format!("{} -{}-> {}\n",
-                                      node.borrow().id,
-                                      edge.transition,
-                                      edge.destination.borrow().id
+                                      node.id,
+                                      edge.symbols,
+                                      self.node(edge.destination).id
                 ))
             }
         }
@@ -160,3 +319,131 @@ impl Display for FiniteAutomaton {
 
 #[derive(Debug)]
 pub struct NotDFAError;
+
+/// Merges the symbols of transitions sharing a source and a destination into a single edge,
+/// keeping the order in which each pair first appears.
+pub fn group_transitions<S: Eq + Hash + Clone>(transitions: Vec<(S, S, SymbolSet)>) -> Vec<(S, S, SymbolSet)> {
+    let mut index: HashMap<(S, S), usize> = HashMap::new();
+    let mut grouped: Vec<(S, S, SymbolSet)> = Vec::new();
+    for (source, destination, symbols) in transitions {
+        match index.get(&(source.clone(), destination.clone())) {
+            Some(idx) => grouped[*idx].2 = grouped[*idx].2.union(&symbols),
+            None => {
+                index.insert((source.clone(), destination.clone()), grouped.len());
+                grouped.push((source, destination, symbols));
+            }
+        }
+    }
+
+    grouped
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_multi_character_state_ids() {
+        let states: Vec<String> = (0..=10).map(|idx| format!("q{}", idx)).collect();
+        let transitions = (0..10)
+            .map(|idx| (states[idx].clone(), states[idx + 1].clone(), 'a'))
+            .collect();
+        let fa = FiniteAutomaton::new(
+            states.clone(),
+            "q0".to_owned(),
+            HashSet::from(["q10".to_owned()]),
+            HashSet::from(['a']),
+            transitions
+        );
+
+        assert!(fa.check_match("aaaaaaaaaa").unwrap());
+        assert!(!fa.check_match("a").unwrap());
+        assert!(fa.to_string().contains("q9 -a-> q10"));
+    }
+
+    #[test]
+    fn test_display() {
+        let fa = FiniteAutomaton::new(vec!["p", "q"], "p", HashSet::from(["q"]), "zyx0".chars().collect(),
+                                      vec![("p", "q", 'y'), ("p", "q", 'x'), ("q", "q", '0')]);
+        assert_eq!(fa.to_string(), "States: p q \nInput state: p\nOutput states: q \nAlphabet: 0 x y z \n\
+                                    Transitions:\np -y-> q\np -x-> q\nq -0-> q\n");
+    }
+
+    #[test]
+    fn test_accepts() {
+        // Words over {a, b} whose second to last letter is a.
+        let fa = FiniteAutomaton::new(
+            vec!["s", "p", "q", "r"],
+            "s",
+            HashSet::from(["r"]),
+            HashSet::from(['a', 'b']),
+            vec![("s", "p", EPSILON), ("p", "p", 'a'), ("p", "p", 'b'), ("p", "q", 'a'), ("q", "r", 'a'), ("q", "r", 'b')]
+        );
+        assert!(fa.check_match("ab").is_err());
+
+        for label in ["ab", "aa", "bbab"] {
+            assert!(fa.accepts(label));
+        }
+        for label in ["", "a", "b", "ba", "abb", "εab"] {
+            assert!(!fa.accepts(label));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Epsilon cannot be part of the alphabet!")]
+    fn test_epsilon_in_alphabet() {
+        FiniteAutomaton::new(vec!["p", "q"], "p", HashSet::from(["q"]), HashSet::from(['a', EPSILON]),
+                             vec![("p", "q", EPSILON)]);
+    }
+
+    #[test]
+    fn test_longest_match() {
+        // Identifiers: a letter followed by letters and digits, with an epsilon transition in between.
+        let letters: Vec<char> = ('a'..='z').collect();
+        let digits: Vec<char> = ('0'..='9').collect();
+        let fa = FiniteAutomaton::new(
+            vec!["p", "q", "r"],
+            "p",
+            HashSet::from(["r"]),
+            letters.iter().chain(&digits).cloned().collect(),
+            letters.iter().map(|ch| ("p", "q", *ch))
+                .chain(letters.iter().chain(&digits).map(|ch| ("r", "r", *ch)))
+                .chain([("q", "r", EPSILON)])
+                .collect()
+        );
+
+        let text = "x1 = ăb2 + 3";
+        assert_eq!(fa.longest_match(text, 0), Some(2));
+        assert_eq!(fa.longest_match(text, 1), None);
+        assert_eq!(fa.longest_match(text, 5), None);
+        assert_eq!(fa.longest_match(text, 7), Some(2));
+        assert_eq!(fa.longest_match(text, text.len()), None);
+        assert_eq!(fa.longest_match(text, 6), None);
+        assert_eq!(fa.longest_match(text, 100), None);
+
+        // The empty prefix counts, and the match only ends at the last final state passed.
+        let fa = FiniteAutomaton::new(vec!["p", "q", "r"], "p", HashSet::from(["p", "r"]), HashSet::from(['a', 'b']),
+                                      vec![("p", "q", 'a'), ("q", "r", 'b'), ("r", "q", 'a')]);
+        assert_eq!(fa.longest_match("b", 0), Some(0));
+        assert_eq!(fa.longest_match("ababa", 0), Some(4));
+    }
+
+    #[test]
+    fn test_clone_and_send() {
+        fn assert_send<T: Send>(_: &T) {}
+
+        let fa = FiniteAutomaton::new(vec!["p", "q"], "p", HashSet::from(["q"]), HashSet::from(['a']), vec![("p", "q", 'a')]);
+        let mut copy = fa.clone();
+        assert_send(&copy);
+        assert_eq!(copy, fa);
+
+        let q = copy.find_state("q").unwrap();
+        assert_eq!(copy.node(copy.start).transitions[0].destination, q);
+        copy.nodes[q.0].is_final_state = false;
+        assert!(fa.check_match("a").unwrap());
+        assert!(!copy.check_match("a").unwrap());
+
+        let handle = std::thread::spawn(move || fa.check_match("a").unwrap());
+        assert!(handle.join().unwrap());
+    }
+}
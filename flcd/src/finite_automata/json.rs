@@ -0,0 +1,169 @@
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use serde::{Deserialize, Serialize};
+use crate::finite_automata::fa::{Edge, EPSILON, FiniteAutomaton};
+use crate::finite_automata::parser::label_symbols;
+use crate::finite_automata::symbol_set::SymbolSet;
+
+/// The JSON shape shared with the course tooling. Symbols are strings holding a single
+/// character or, for edges labelled with several symbols, a label as in FA files (`a-z`).
+#[derive(Debug, Serialize, Deserialize)]
+struct JsonAutomaton {
+    states: Vec<String>,
+    start: String,
+    finals: Vec<String>,
+    alphabet: Vec<String>,
+    transitions: Vec<JsonTransition>
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JsonTransition {
+    from: String,
+    to: String,
+    symbol: String
+}
+
+#[derive(Debug)]
+pub enum JsonError {
+    Syntax(serde_json::Error),
+    DuplicateState { state: String },
+    UnknownState { state: String },
+    InvalidSymbol { symbol: String },
+    SymbolNotInAlphabet { symbol: char }
+}
+
+impl Display for JsonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonError::Syntax(err) => write!(f, "invalid JSON automaton: {}", err),
+            JsonError::DuplicateState { state } => write!(f, "state {} is declared twice", state),
+            JsonError::UnknownState { state } => write!(f, "unknown state {}", state),
+            JsonError::InvalidSymbol { symbol } => write!(f, "{} is not a symbol, a range or a class", symbol),
+            JsonError::SymbolNotInAlphabet { symbol } => write!(f, "symbol {} is not in the alphabet", symbol)
+        }
+    }
+}
+
+impl std::error::Error for JsonError {}
+
+fn check_state(states: &HashSet<&str>, state: &str) -> Result<(), JsonError> {
+    if states.contains(state) {
+        Ok(())
+    } else {
+        Err(JsonError::UnknownState { state: state.to_owned() })
+    }
+}
+
+fn symbol(edge: &Edge) -> String {
+    match edge.symbols.ranges() {
+        [(start, end)] if start == end => start.to_string(),
+        _ => edge.symbols.to_string()
+    }
+}
+
+impl FiniteAutomaton {
+    pub fn from_json(content: &str) -> Result<FiniteAutomaton, JsonError> {
+        let json: JsonAutomaton = serde_json::from_str(content).map_err(JsonError::Syntax)?;
+
+        let mut declared = HashSet::new();
+        for state in &json.states {
+            if !declared.insert(state.as_str()) {
+                return Err(JsonError::DuplicateState { state: state.clone() });
+            }
+        }
+        check_state(&declared, &json.start)?;
+        for state in &json.finals {
+            check_state(&declared, state)?;
+        }
+
+        let mut alphabet = SymbolSet::new();
+        for label in &json.alphabet {
+            match label_symbols(label, None) {
+                Some(symbols) if !symbols.contains(EPSILON) => alphabet = alphabet.union(&symbols),
+                _ => return Err(JsonError::InvalidSymbol { symbol: label.clone() })
+            }
+        }
+
+        let mut transitions = Vec::new();
+        for transition in &json.transitions {
+            check_state(&declared, &transition.from)?;
+            check_state(&declared, &transition.to)?;
+
+            let symbols = label_symbols(&transition.symbol, Some(&alphabet))
+                .ok_or_else(|| JsonError::InvalidSymbol { symbol: transition.symbol.clone() })?;
+            if symbols != SymbolSet::single(EPSILON) {
+                if let Some(symbol) = symbols.difference(&alphabet).iter().next() {
+                    return Err(JsonError::SymbolNotInAlphabet { symbol });
+                }
+            }
+
+            transitions.push((transition.from.as_str(), transition.to.as_str(), symbols));
+        }
+
+        let final_states = json.finals.iter().map(String::as_str).collect();
+        let states = json.states.iter().map(String::as_str).collect();
+        Ok(FiniteAutomaton::with_symbol_sets(states, json.start.as_str(), final_states, alphabet.iter().collect(), transitions))
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut alphabet: Vec<char> = self.alphabet.iter().cloned().collect();
+        alphabet.sort();
+
+        let json = JsonAutomaton {
+            states: self.nodes.iter().map(|node| node.id.clone()).collect(),
+            start: self.node(self.start).id.clone(),
+            finals: self.nodes.iter().filter(|node| node.is_final_state).map(|node| node.id.clone()).collect(),
+            alphabet: alphabet.iter().map(|symbol| symbol.to_string()).collect(),
+            transitions: self.nodes.iter()
+                .flat_map(|node| node.transitions.iter().map(move |edge| (node, edge)))
+                .map(|(node, edge)| JsonTransition {
+                    from: node.id.clone(),
+                    to: self.node(edge.destination).id.clone(),
+                    symbol: symbol(edge)
+                })
+                .collect()
+        };
+
+        serde_json::to_string_pretty(&json).expect("automata can always be written as JSON")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::finite_automata::parser::parse_fa_str;
+
+    #[test]
+    fn test_json_round_trip() {
+        let fa = parse_fa_str("p,q,r\np\nr\n0-9,\\,,a\np,q,\\,;q,q,0-9;q,r,a;r,p,ε").unwrap();
+        let json = fa.to_json();
+        assert!(json.contains("\"symbol\": \",\""));
+        assert!(json.contains("\"symbol\": \"0-9\""));
+
+        let parsed = FiniteAutomaton::from_json(&json).unwrap();
+        assert_eq!(parsed, fa);
+    }
+
+    #[test]
+    fn test_from_json() {
+        let fa = FiniteAutomaton::from_json(r#"{
+            "states": ["p", "q"],
+            "start": "p",
+            "finals": ["q"],
+            "alphabet": ["a", "b"],
+            "transitions": [{"from": "p", "to": "q", "symbol": "a"}, {"from": "q", "to": "q", "symbol": "[ab]"}]
+        }"#).unwrap();
+        assert!(fa.check_match("abba").unwrap());
+        assert!(!fa.check_match("b").unwrap());
+
+        let error = |content: &str| FiniteAutomaton::from_json(content).unwrap_err();
+        assert!(matches!(error("{\"states\": []}"), JsonError::Syntax(_)));
+        assert!(matches!(error(r#"{"states": ["p"], "start": "q", "finals": [], "alphabet": [], "transitions": []}"#),
+                         JsonError::UnknownState { .. }));
+        assert!(matches!(error(r#"{"states": ["p"], "start": "p", "finals": [], "alphabet": ["ab"], "transitions": []}"#),
+                         JsonError::InvalidSymbol { .. }));
+        assert!(matches!(error(r#"{"states": ["p"], "start": "p", "finals": [], "alphabet": ["a"],
+                                   "transitions": [{"from": "p", "to": "p", "symbol": "b"}]}"#),
+                         JsonError::SymbolNotInAlphabet { symbol: 'b' }));
+    }
+}
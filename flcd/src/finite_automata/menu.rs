@@ -1,35 +1,46 @@
+use std::fs;
 use std::io;
-use crate::finite_automata::fa::FiniteAutomaton;
+use crate::finite_automata::equivalence::Equivalence;
+use crate::finite_automata::fa::{FiniteAutomaton, NotDFAError};
 use crate::finite_automata::parser::parse_fa;
+use crate::finite_automata::writer::write_fa;
 
-fn read_fa() -> FiniteAutomaton {
-    println!("File path: ");
+fn read_line(prompt: &str) -> String {
+    println!("{}", prompt);
 
     let mut input_text= String::new();
     io::stdin()
         .read_line(&mut input_text)
         .expect("failed to read from stdin");
 
-    let trimmed = input_text.trim();
-
-    parse_fa(trimmed)
+    input_text.trim().to_owned()
 }
 
-fn check_label(fa: &FiniteAutomaton) {
-    if !fa.is_dfa() {
-        println!("This is not a DFA");
-        return;
+fn read_fa() -> Option<FiniteAutomaton> {
+    let trimmed = read_line("File path: ");
+
+    match parse_fa(&trimmed) {
+        Ok(fa) => Some(fa),
+        Err(err) => {
+            println!("{}: {}", trimmed, err);
+            None
+        }
     }
+}
 
-    println!("Label: ");
+fn check_label(fa: &FiniteAutomaton) {
+    let trimmed = read_line("Label: ");
 
-    let mut input_text= String::new();
-    io::stdin()
-        .read_line(&mut input_text)
-        .expect("failed to read from stdin");
+    let trace = match fa.check_match_traced(&trimmed) {
+        Ok(trace) => trace,
+        Err(NotDFAError) => {
+            println!("This is not a DFA, tracing its subset construction");
+            fa.to_dfa().check_match_traced(&trimmed).unwrap()
+        }
+    };
+    println!("{}", trace);
 
-    let trimmed = input_text.trim();
-    if fa.check_match(trimmed).unwrap() {
+    if fa.accepts(&trimmed) {
         println!("It's a match!");
     }
     else {
@@ -37,13 +48,142 @@ fn check_label(fa: &FiniteAutomaton) {
     }
 }
 
+fn convert_to_dfa(fa: &FiniteAutomaton) -> FiniteAutomaton {
+    let composite = fa.to_dfa_with_states();
+    for (id, set) in &composite.states {
+        let members: Vec<String> = set.iter().map(|state| state.to_string()).collect();
+        println!("{} = {{{}}}", id, members.join(", "));
+    }
+
+    let dfa = fa.to_dfa();
+    println!("{}", dfa);
+    dfa
+}
+
+fn minimize(fa: &FiniteAutomaton) -> FiniteAutomaton {
+    let (minimal, report) = fa.minimize();
+    println!("States before: {}", report.states_before);
+    println!("States after: {}", report.states_after());
+    for (id, members) in &report.merged {
+        let members: Vec<String> = members.iter().map(|state| state.to_string()).collect();
+        println!("{} = {{{}}}", id, members.join(", "));
+    }
+
+    if !report.removed.is_empty() {
+        let removed: Vec<String> = report.removed.iter().map(|state| state.to_string()).collect();
+        println!("Removed: {}", removed.join(", "));
+    }
+
+    println!("{}", minimal);
+    minimal
+}
+
+fn trim(fa: &FiniteAutomaton) -> FiniteAutomaton {
+    let (trimmed, report) = fa.trim();
+    if report.is_empty() {
+        println!("Every state is reachable and can reach a final state");
+    }
+    if !report.unreachable.is_empty() {
+        println!("Unreachable: {}", report.unreachable.join(", "));
+    }
+    if !report.dead.is_empty() {
+        println!("Dead: {}", report.dead.join(", "));
+    }
+
+    println!("{}", trimmed);
+    trimmed
+}
+
+fn export_dot(fa: &FiniteAutomaton) {
+    let file_name = read_line("DOT file path: ");
+    match fs::write(&file_name, fa.to_dot()) {
+        Ok(()) => println!("Written to {}", file_name),
+        Err(err) => println!("{}: {}", file_name, err)
+    }
+}
+
+fn save_fa(fa: &FiniteAutomaton) {
+    let file_name = read_line("File path (ending in .json to save as JSON): ");
+    match write_fa(fa, &file_name) {
+        Ok(()) => println!("Written to {}", file_name),
+        Err(err) => println!("{}: {}", file_name, err)
+    }
+}
+
+fn check_equivalence(fa: &FiniteAutomaton) {
+    let Some(other) = read_fa() else { return };
+
+    match fa.equivalent(&other) {
+        Equivalence::Equivalent => println!("The automata are equivalent"),
+        Equivalence::Counterexample(word) => {
+            let owner = if fa.accepts(&word) { "current" } else { "other" };
+            println!("Not equivalent: \"{}\" is only accepted by the {} FA", word, owner);
+        }
+    }
+}
+
+fn analyse_language(fa: &FiniteAutomaton) {
+    if fa.is_empty() {
+        println!("The language is empty");
+        return;
+    }
+
+    println!("The language is {}", if fa.is_finite() { "finite" } else { "infinite" });
+    println!("Shortest word: \"{}\"", fa.shortest_accepted().unwrap());
+
+    let count = read_line("Number of words to list: ");
+    let Ok(count) = count.parse::<usize>() else {
+        println!("{} is not a number", count);
+        return;
+    };
+    for word in fa.accepted_words().take(count) {
+        println!("\"{}\"", word);
+    }
+}
+
+fn search_file(fa: &FiniteAutomaton) {
+    let file_name = read_line("File to search: ");
+    let text = match fs::read_to_string(&file_name) {
+        Ok(text) => text,
+        Err(err) => {
+            println!("{}: {}", file_name, err);
+            return;
+        }
+    };
+    let overlapping = read_line("Report overlapping matches? (y/n): ") == "y";
+
+    let matches = fa.find_matches(&text, overlapping);
+    for found in &matches {
+        println!("{}", found);
+    }
+    println!("{} matches", matches.len());
+}
+
+fn display_fa(fa: &FiniteAutomaton) {
+    match read_line("View (1. list of transitions, 2. transition table): ").as_str() {
+        "2" => print!("{}", fa.to_table()),
+        _ => println!("{}", fa)
+    }
+}
+
 pub fn main_menu() {
     let mut fa = None;
     loop {
         println!("Choose an option:");
         println!("1. Read FA");
         println!("2. Display FA");
-        println!("3. For a DFA, verifies if a sequence is accepted by the FA");
+        println!("3. Verify if a sequence is accepted by the FA");
+        println!("4. Convert the FA to a DFA");
+        println!("5. Minimize the FA");
+        println!("6. Export the FA as a DOT file");
+        println!("7. Check if the FA is equivalent to another FA");
+        println!("8. Convert the FA to a regular expression");
+        println!("9. Save the FA to a file");
+        println!("10. Remove unreachable and dead states");
+        println!("11. Complete the FA with a sink state");
+        println!("12. Complement the FA");
+        println!("13. List the words accepted by the FA");
+        println!("14. Search a file for the words accepted by the FA");
 
         let mut input_text = String::new();
         io::stdin()
@@ -51,11 +191,38 @@ pub fn main_menu() {
             .expect("failed to read from stdin");
 
         let trimmed = input_text.trim();
+        if trimmed != "1" && fa.is_none() {
+            println!("Read an FA first!");
+            continue;
+        }
 
         match trimmed {
-            "1" => fa = Some(read_fa()),
-            "2" => println!("{}", fa.as_ref().unwrap()),
+            "1" => {
+                if let Some(new_fa) = read_fa() {
+                    fa = Some(new_fa);
+                }
+            },
+            "2" => display_fa(fa.as_ref().unwrap()),
             "3" => check_label(fa.as_ref().unwrap()),
+            "4" => fa = Some(convert_to_dfa(fa.as_ref().unwrap())),
+            "5" => fa = Some(minimize(fa.as_ref().unwrap())),
+            "6" => export_dot(fa.as_ref().unwrap()),
+            "7" => check_equivalence(fa.as_ref().unwrap()),
+            "8" => println!("{}", fa.as_ref().unwrap().to_regex()),
+            "9" => save_fa(fa.as_ref().unwrap()),
+            "10" => fa = Some(trim(fa.as_ref().unwrap())),
+            "11" => {
+                let complete = fa.as_ref().unwrap().complete();
+                println!("{}", complete);
+                fa = Some(complete);
+            },
+            "12" => {
+                let complement = fa.as_ref().unwrap().complement();
+                println!("{}", complement);
+                fa = Some(complement);
+            },
+            "13" => analyse_language(fa.as_ref().unwrap()),
+            "14" => search_file(fa.as_ref().unwrap()),
             _ => println!("Invalid option!")
         }
     }
@@ -0,0 +1,195 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use crate::finite_automata::fa::{group_transitions, FiniteAutomaton};
+
+/// Which states of the (deterministic) input ended up in each state of the minimal DFA.
+#[derive(Debug)]
+pub struct MinimizationReport {
+    pub states_before: usize,
+    pub merged: Vec<(String, Vec<String>)>,
+    pub removed: Vec<String>
+}
+
+impl MinimizationReport {
+    pub fn states_after(&self) -> usize {
+        self.merged.len()
+    }
+}
+
+impl FiniteAutomaton {
+    /// Builds the minimal DFA of `self` by partition refinement. Unreachable states and states
+    /// from which no final state can be reached are dropped. Nondeterministic automata are
+    /// determinized first, in which case the report refers to the states of `to_dfa()`.
+    pub fn minimize(&self) -> (FiniteAutomaton, MinimizationReport) {
+        if !self.is_dfa() {
+            return self.to_dfa().minimize();
+        }
+
+        let ids: Vec<String> = self.nodes.iter().map(|node| node.id.clone()).collect();
+
+        // Symbols of the same class behave the same way, so the table has a column per class.
+        let classes = self.symbol_classes();
+
+        // Missing transitions go to an implicit sink state, stored last.
+        let sink = ids.len();
+        let mut delta = vec![vec![sink; classes.len()]; ids.len() + 1];
+        for (source, node) in self.nodes.iter().enumerate() {
+            for edge in &node.transitions {
+                for (class_idx, class) in classes.iter().enumerate() {
+                    if edge.symbols.contains(class.ranges()[0].0) {
+                        delta[source][class_idx] = edge.destination.0;
+                    }
+                }
+            }
+        }
+
+        let start = self.start.0;
+        let mut reachable = vec![false; ids.len() + 1];
+        let mut queue = VecDeque::from([start]);
+        reachable[start] = true;
+        while let Some(state) = queue.pop_front() {
+            for &next in &delta[state] {
+                if !reachable[next] {
+                    reachable[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+        reachable[sink] = true;
+
+        let is_final: Vec<bool> = self.nodes.iter()
+            .map(|node| node.is_final_state)
+            .chain([false])
+            .collect();
+
+        let mut block: Vec<usize> = (0..=ids.len()).map(|state| usize::from(is_final[state])).collect();
+        let mut block_count = 0;
+        loop {
+            let mut signatures: HashMap<(usize, Vec<usize>), usize> = HashMap::new();
+            let mut next_block = vec![0; ids.len() + 1];
+            for state in (0..=ids.len()).filter(|state| reachable[*state]) {
+                let signature = (block[state], delta[state].iter().map(|next| block[*next]).collect());
+                let len = signatures.len();
+                next_block[state] = *signatures.entry(signature).or_insert(len);
+            }
+
+            let stable = signatures.len() == block_count;
+            block_count = signatures.len();
+            block = next_block;
+            if stable {
+                break;
+            }
+        }
+
+        let dead_block = block[sink];
+        let mut members: Vec<Vec<usize>> = vec![Vec::new(); block_count];
+        let mut removed = Vec::new();
+        for state in 0..ids.len() {
+            if !reachable[state] || block[state] == dead_block {
+                removed.push(ids[state].clone());
+            } else {
+                members[block[state]].push(state);
+            }
+        }
+
+        let start_block = block[start];
+        let representative = |block: usize| ids[members[block][0]].clone();
+        let blocks: Vec<usize> = (0..block_count).filter(|block| !members[*block].is_empty()).collect();
+
+        if start_block == dead_block {
+            // The language is empty, so the minimal DFA is a single non-final state.
+            let id = ids[start].clone();
+            removed.retain(|state| *state != id);
+            let fa = FiniteAutomaton::new(vec![id.as_str()], id.as_str(), HashSet::new(), self.alphabet.clone(), Vec::new());
+            let report = MinimizationReport {
+                states_before: ids.len(),
+                merged: vec![(id.clone(), vec![id])],
+                removed
+            };
+            return (fa, report);
+        }
+
+        let mut states = Vec::new();
+        let mut final_states = HashSet::new();
+        let mut transitions = Vec::new();
+        let mut merged = Vec::new();
+        for &block_idx in &blocks {
+            let id = representative(block_idx);
+            let first = members[block_idx][0];
+            states.push(id.clone());
+            if is_final[first] {
+                final_states.insert(id.clone());
+            }
+
+            for (class_idx, class) in classes.iter().enumerate() {
+                let next_block = block[delta[first][class_idx]];
+                if next_block != dead_block {
+                    transitions.push((id.clone(), representative(next_block), class.clone()));
+                }
+            }
+
+            merged.push((id, members[block_idx].iter().map(|state| ids[*state].clone()).collect()));
+        }
+
+        let fa = FiniteAutomaton::with_symbol_sets(states,
+                                                    representative(start_block),
+                                                    final_states,
+                                                    self.alphabet.clone(),
+                                                    group_transitions(transitions));
+        let report = MinimizationReport {
+            states_before: ids.len(),
+            merged,
+            removed
+        };
+
+        (fa, report)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_minimize() {
+        // q and r are equivalent, u is unreachable and d is dead.
+        let fa = FiniteAutomaton::new(
+            vec!["p", "q", "r", "u", "d"],
+            "p",
+            HashSet::from(["q", "r"]),
+            HashSet::from(['0', '1']),
+            vec![("p", "q", '0'), ("p", "r", '1'), ("q", "r", '0'), ("r", "q", '0'),
+                 ("q", "d", '1'), ("d", "d", '0'), ("u", "p", '0')]
+        );
+
+        let (minimal, report) = fa.minimize();
+        assert_eq!(report.states_before, 5);
+        assert_eq!(report.states_after(), 2);
+        assert_eq!(report.merged, vec![("p".to_owned(), vec!["p".to_owned()]),
+                                       ("q".to_owned(), vec!["q".to_owned(), "r".to_owned()])]);
+        assert_eq!(report.removed, vec!["u", "d"]);
+
+        for label in ["0", "1", "00", "10", "000"] {
+            assert!(minimal.check_match(label).unwrap());
+        }
+        for label in ["", "01", "11"] {
+            assert!(!minimal.check_match(label).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_minimize_empty_language() {
+        let fa = FiniteAutomaton::new(
+            vec!["p", "q"],
+            "p",
+            HashSet::new(),
+            HashSet::from(['a']),
+            vec![("p", "q", 'a')]
+        );
+
+        let (minimal, report) = fa.minimize();
+        assert_eq!(minimal.nodes.len(), 1);
+        assert_eq!(report.removed, vec!["q"]);
+        assert!(!minimal.check_match("").unwrap());
+        assert!(!minimal.check_match("a").unwrap());
+    }
+}
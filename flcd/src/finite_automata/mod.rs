@@ -1,4 +1,24 @@
-
 pub mod fa;
+pub mod symbol_set;
 pub mod menu;
-pub mod parser;
\ No newline at end of file
+pub mod parser;
+pub mod determinization;
+pub mod minimization;
+pub mod dot;
+pub mod product;
+pub mod equivalence;
+pub mod regular_expression;
+pub mod state_elimination;
+pub mod regex_parser;
+pub mod thompson;
+pub mod trace;
+pub mod compiled;
+pub mod writer;
+pub mod json;
+pub mod trim;
+pub mod completion;
+pub mod regular_operations;
+pub mod analysis;
+pub mod search;
+pub mod canonical;
+pub mod table;
\ No newline at end of file
@@ -1,31 +1,365 @@
 use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
 use std::fs::read_to_string;
-use crate::finite_automata::fa::FiniteAutomaton;
+use crate::finite_automata::fa::{EPSILON, FiniteAutomaton};
+use crate::finite_automata::json::JsonError;
+use crate::finite_automata::regex_parser::RegexError;
+use crate::finite_automata::symbol_set::SymbolSet;
 
-fn parse_truple(truple: &&str) -> (char, char, char) {
-    let item_string: Vec<&str> = truple.split(',').collect();
-    let items: Vec<char> = item_string.iter().map(|x| x.chars().nth(0).unwrap()).collect();
-    (items[0], items[1], items[2])
+const LINE_NAMES: [&str; 5] = ["states", "start state", "final states", "alphabet", "transitions"];
+
+#[derive(Debug)]
+pub enum ParseError {
+    Io(std::io::Error),
+    Regex(RegexError),
+    Json(JsonError),
+    MissingLine { line: usize, column: usize },
+    EmptyField { line: usize, column: usize },
+    DuplicateState { state: String, line: usize, column: usize },
+    MissingStartState { line: usize, column: usize },
+    UnknownState { state: String, line: usize, column: usize },
+    InvalidSymbol { symbol: String, line: usize, column: usize },
+    SymbolNotInAlphabet { symbol: char, line: usize, column: usize },
+    MalformedTransition { transition: String, line: usize, column: usize },
+    UnexpectedLine { line: usize, column: usize }
+}
+
+impl ParseError {
+    pub fn position(&self) -> Option<(usize, usize)> {
+        match self {
+            ParseError::Io(_) | ParseError::Regex(_) | ParseError::Json(_) => None,
+            ParseError::MissingLine { line, column }
+            | ParseError::EmptyField { line, column }
+            | ParseError::DuplicateState { line, column, .. }
+            | ParseError::MissingStartState { line, column }
+            | ParseError::UnknownState { line, column, .. }
+            | ParseError::InvalidSymbol { line, column, .. }
+            | ParseError::SymbolNotInAlphabet { line, column, .. }
+            | ParseError::MalformedTransition { line, column, .. }
+            | ParseError::UnexpectedLine { line, column } => Some((*line, *column))
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some((line, column)) = self.position() {
+            write!(f, "{}:{}: ", line, column)?;
+        }
+
+        match self {
+            ParseError::Io(err) => write!(f, "could not read the file: {}", err),
+            ParseError::Regex(err) => write!(f, "{}", err),
+            ParseError::Json(err) => write!(f, "{}", err),
+            ParseError::MissingLine { line, .. } => write!(f, "missing the {} line", LINE_NAMES[line - 1]),
+            ParseError::EmptyField { .. } => write!(f, "empty field"),
+            ParseError::DuplicateState { state, .. } => write!(f, "state {} is declared twice", state),
+            ParseError::MissingStartState { .. } => write!(f, "missing start state"),
+            ParseError::UnknownState { state, .. } => write!(f, "unknown state {}", state),
+            ParseError::InvalidSymbol { symbol, .. } => write!(f, "{} is not a symbol, a range or a class", symbol),
+            ParseError::SymbolNotInAlphabet { symbol, .. } => write!(f, "symbol {} is not in the alphabet", symbol),
+            ParseError::MalformedTransition { transition, .. } =>
+                write!(f, "{} is not a source,destination,symbol triple", transition),
+            ParseError::UnexpectedLine { .. } => write!(f, "unexpected line after the transitions")
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Trims the whitespace around `field`, except for a whitespace character escaped with `\`.
+fn trim_field(field: &str) -> &str {
+    let field = field.trim_start();
+    let trimmed = field.trim_end();
+    let backslashes = trimmed.chars().rev().take_while(|ch| *ch == '\\').count();
+    match field[trimmed.len()..].chars().next() {
+        Some(escaped) if backslashes % 2 == 1 => &field[..trimmed.len() + escaped.len_utf8()],
+        _ => trimmed
+    }
+}
+
+/// Splits `line` on `separator`, returning each trimmed field with its 1-based column. A
+/// separator preceded by `\` is part of the field.
+fn fields(line: &str, separator: char) -> Vec<(usize, &str)> {
+    let mut res = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+    let ends = line.char_indices()
+        .filter(|(_, ch)| {
+            let is_separator = *ch == separator && !escaped;
+            escaped = *ch == '\\' && !escaped;
+            is_separator
+        })
+        .map(|(idx, _)| idx)
+        .chain([line.len()]);
+
+    for end in ends {
+        let field = &line[start..end];
+        let leading = field.len() - field.trim_start().len();
+        res.push((line[..start + leading].chars().count() + 1, trim_field(field)));
+        start = end + separator.len_utf8();
+    }
+
+    res
+}
+
+/// Fields of a list line; a blank line is an empty list.
+fn list_fields(line: &str, separator: char, line_num: usize) -> Result<Vec<(usize, &str)>, ParseError> {
+    if line.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let res = fields(line, separator);
+    if let Some((column, _)) = res.iter().find(|(_, field)| field.is_empty()) {
+        return Err(ParseError::EmptyField { line: line_num, column: *column });
+    }
+
+    Ok(res)
+}
+
+/// Parses the items of a bracketed set, e.g. `a-z_[:digit:]`. Returns `None` if it is malformed.
+fn parse_set(chars: &[char]) -> Option<SymbolSet> {
+    let mut res = SymbolSet::new();
+    let mut position = 0;
+    let next_char = |position: &mut usize| -> Option<char> {
+        let ch = *chars.get(*position)?;
+        *position += 1;
+        if ch != '\\' {
+            return Some(ch);
+        }
+
+        *position += 1;
+        chars.get(*position - 1).cloned()
+    };
+
+    while position < chars.len() {
+        if chars[position..].starts_with(&['[', ':']) {
+            let name_end = (position + 2..chars.len()).find(|idx| chars[*idx..].starts_with(&[':', ']']))?;
+            let name: String = chars[position + 2..name_end].iter().collect();
+            res = res.union(&SymbolSet::class(&name)?);
+            position = name_end + 2;
+            continue;
+        }
+
+        let start = next_char(&mut position)?;
+        let end = if chars.get(position) == Some(&'-') && position + 1 < chars.len() {
+            position += 1;
+            next_char(&mut position)?
+        } else {
+            start
+        };
+
+        if end < start {
+            return None;
+        }
+        res = res.union(&SymbolSet::range(start, end));
+    }
+
+    if res.is_empty() {
+        return None;
+    }
+
+    Some(res)
 }
 
-pub fn parse_fa(file_name: &str) -> FiniteAutomaton {
-    let lines: Vec<String> = read_to_string(file_name)
-        .unwrap()
-        .lines()
-        .map(String::from)
+/// The symbols of a label: a symbol `a` (or `\,` for a separator), a range `a-z`, a class
+/// `[:alpha:]` or a set `[a-z_]`. Sets starting with `^` are negated, keeping the symbols of
+/// `universe` that are not listed; they are only allowed where there is a universe, i.e. on
+/// transitions. Returns `None` if the label is malformed.
+pub fn label_symbols(label: &str, universe: Option<&SymbolSet>) -> Option<SymbolSet> {
+    let chars: Vec<char> = label.chars().collect();
+    match chars.as_slice() {
+        [ch] => Some(SymbolSet::single(*ch)),
+        ['\\', ch] => Some(SymbolSet::single(*ch)),
+        [start, '-', end] if start <= end => Some(SymbolSet::range(*start, *end)),
+        ['[', ':', name @ .., ':', ']'] => SymbolSet::class(&name.iter().collect::<String>()),
+        ['[', '^', items @ .., ']'] => universe.zip(parse_set(items)).map(|(universe, set)| universe.difference(&set)),
+        ['[', items @ .., ']'] => parse_set(items),
+        _ => None
+    }
+}
+
+fn parse_label(label: &str, universe: Option<&SymbolSet>, line: usize, column: usize) -> Result<SymbolSet, ParseError> {
+    label_symbols(label, universe).ok_or_else(|| ParseError::InvalidSymbol { symbol: label.to_owned(), line, column })
+}
+
+fn check_state(states: &HashSet<&str>, state: &str, line: usize, column: usize) -> Result<(), ParseError> {
+    if states.contains(state) {
+        Ok(())
+    } else {
+        Err(ParseError::UnknownState { state: state.to_owned(), line, column })
+    }
+}
+
+fn parse_truple<'a>(truple: &'a str, states: &HashSet<&str>, alphabet: &SymbolSet, line: usize, column: usize)
+    -> Result<(&'a str, &'a str, SymbolSet), ParseError> {
+    let items: Vec<(usize, &str)> = fields(truple, ',')
+        .into_iter()
+        .map(|(item_column, item)| (column + item_column - 1, item))
         .collect();
 
-    let states_string: Vec<&str> = lines[0].split(',').collect();
-    let states: Vec<char> = states_string.iter().map(|x| x.chars().nth(0).unwrap()).collect();
+    if items.len() != 3 || items.iter().any(|(_, item)| item.is_empty()) {
+        return Err(ParseError::MalformedTransition { transition: truple.trim().to_owned(), line, column });
+    }
+
+    check_state(states, items[0].1, line, items[0].0)?;
+    check_state(states, items[1].1, line, items[1].0)?;
+
+    let symbols = parse_label(items[2].1, Some(alphabet), line, items[2].0)?;
+    if symbols != SymbolSet::single(EPSILON) {
+        if let Some(symbol) = symbols.difference(alphabet).iter().next() {
+            return Err(ParseError::SymbolNotInAlphabet { symbol, line, column: items[2].0 });
+        }
+    }
+
+    Ok((items[0].1, items[1].1, symbols))
+}
+
+pub fn parse_fa_str(content: &str) -> Result<FiniteAutomaton, ParseError> {
+    let lines: Vec<&str> = content.split('\n').map(|line| line.trim_end_matches('\r')).collect();
+    if lines.len() < LINE_NAMES.len() {
+        return Err(ParseError::MissingLine { line: lines.len() + 1, column: 1 });
+    }
+
+    let mut states = Vec::new();
+    let mut declared = HashSet::new();
+    for (column, state) in list_fields(lines[0], ',', 1)? {
+        if !declared.insert(state) {
+            return Err(ParseError::DuplicateState { state: state.to_owned(), line: 1, column });
+        }
+        states.push(state);
+    }
+
+    let start_state = lines[1].trim();
+    if start_state.is_empty() {
+        return Err(ParseError::MissingStartState { line: 2, column: 1 });
+    }
+    let start_column = lines[1].chars().take_while(|ch| ch.is_whitespace()).count() + 1;
+    check_state(&declared, start_state, 2, start_column)?;
+
+    let mut final_states = HashSet::new();
+    for (column, state) in list_fields(lines[2], ',', 3)? {
+        check_state(&declared, state, 3, column)?;
+        final_states.insert(state);
+    }
 
-    let final_states_string: Vec<&str> = lines[2].split(',').collect();
-    let final_states: HashSet<char> = final_states_string.iter().map(|x| x.chars().nth(0).unwrap()).collect();
+    let mut alphabet = SymbolSet::new();
+    for (column, label) in list_fields(lines[3], ',', 4)? {
+        let symbols = parse_label(label, None, 4, column)?;
+        if symbols.contains(EPSILON) {
+            return Err(ParseError::InvalidSymbol { symbol: label.to_owned(), line: 4, column });
+        }
+        alphabet = alphabet.union(&symbols);
+    }
 
-    let alphabet_string: Vec<&str> = lines[3].split(',').collect();
-    let alphabet: HashSet<char> = alphabet_string.iter().map(|x| x.chars().nth(0).unwrap()).collect();
+    let mut transitions = Vec::new();
+    for (column, truple) in list_fields(lines[4], ';', 5)? {
+        transitions.push(parse_truple(truple, &declared, &alphabet, 5, column)?);
+    }
+
+    let extra = lines.iter()
+        .enumerate()
+        .skip(LINE_NAMES.len())
+        .find(|(_, line)| !line.trim().is_empty());
+    if let Some((idx, line)) = extra {
+        let column = line.chars().take_while(|ch| ch.is_whitespace()).count() + 1;
+        return Err(ParseError::UnexpectedLine { line: idx + 1, column });
+    }
+
+    Ok(FiniteAutomaton::with_symbol_sets(states, start_state, final_states, alphabet.iter().collect(), transitions))
+}
 
-    let transitions_string: Vec<&str> = lines[4].split(';').collect();
-    let transitions: Vec<(char, char, char)> = transitions_string.iter().map(parse_truple).collect();
+/// Reads an automaton from `file_name`. Files ending in `.re` hold a single regular expression,
+/// files ending in `.json` a JSON automaton, anything else is in the five-line format described
+/// in `fa_file_ebnf`.
+pub fn parse_fa(file_name: &str) -> Result<FiniteAutomaton, ParseError> {
+    let content = read_to_string(file_name).map_err(ParseError::Io)?;
+    if file_name.ends_with(".re") {
+        let pattern = content.trim_end_matches(['\r', '\n']);
+        return FiniteAutomaton::from_regex(pattern).map_err(ParseError::Regex);
+    }
+    if file_name.ends_with(".json") {
+        return FiniteAutomaton::from_json(&content).map_err(ParseError::Json);
+    }
 
-    FiniteAutomaton::new(states, lines[1].chars().nth(0).unwrap(), final_states, alphabet, transitions)
-}
\ No newline at end of file
+    parse_fa_str(&content)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse_err(content: &str) -> ParseError {
+        parse_fa_str(content).unwrap_err()
+    }
+
+    #[test]
+    fn test_parse() {
+        let fa = parse_fa_str("p, q10,r\np\nr\n0,1\np,q10,1; q10,q10,0;q10,r,1;r,r,0").unwrap();
+        assert_eq!(fa.nodes.len(), 3);
+        assert!(fa.check_match("1001").unwrap());
+        assert!(!fa.check_match("10").unwrap());
+    }
+
+    #[test]
+    fn test_parse_labels() {
+        let fa = parse_fa_str("p,q\np\nq\n[:alpha:],_,0-9\np,q,[[:alpha:]_];q,q,[:word:]").unwrap();
+        assert_eq!(fa.alphabet.len(), 63);
+        assert_eq!(fa.transitions().len(), 116);
+        assert!(fa.is_dfa());
+        assert!(fa.check_match("_id42").unwrap());
+        assert!(!fa.check_match("4id").unwrap());
+        assert!(fa.to_string().contains("p -[A-Z_a-z]-> q"));
+
+        let fa = parse_fa_str("p,q\np\nq\na-z\np,q,[^aeiou];p,p,[aeiou];q,q,b-d").unwrap();
+        assert!(fa.is_dfa());
+        assert!(fa.check_match("aeb").unwrap());
+        assert!(fa.check_match("ab").unwrap());
+        assert!(!fa.check_match("bf").unwrap());
+
+        let fa = parse_fa_str("p,q\np\nq\na-z\np,q,a-m;p,q,[xk-m]").unwrap();
+        assert!(!fa.is_dfa());
+    }
+
+    #[test]
+    fn test_parse_escaped_space() {
+        let fa = parse_fa_str("p,q\np\nq\na,\\ \np,q,\\ ; q,q,a").unwrap();
+        assert_eq!(fa.alphabet, HashSet::from(['a', ' ']));
+        assert!(fa.check_match(" aa").unwrap());
+
+        let fa = parse_fa_str("p,q\np\nq\na,\\\\ \np,q,\\\\ ").unwrap();
+        assert_eq!(fa.alphabet, HashSet::from(['a', '\\']));
+    }
+
+    #[test]
+    fn test_parse_string_constants() {
+        let fa = parse_fa("string.re").unwrap();
+        for label in ["\"\"", "\"abc\"", "\"a b\tc\"", "\"~!#\"", "\"ă\"", "\"a\"b\""] {
+            assert!(fa.accepts(label), "{}", label);
+        }
+
+        for label in ["abc", "\"abc", "\"a\nb\""] {
+            assert!(!fa.accepts(label), "{}", label);
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(parse_err("p,q\np\nq\n0"), ParseError::MissingLine { line: 5, column: 1 }));
+        assert!(matches!(parse_err("p,q,p\np\nq\n0\n"), ParseError::DuplicateState { line: 1, column: 5, .. }));
+        assert!(matches!(parse_err("p,q\n \nq\n0\n"), ParseError::MissingStartState { line: 2, column: 1 }));
+        assert!(matches!(parse_err("p,q\nr\nq\n0\n"), ParseError::UnknownState { line: 2, column: 1, .. }));
+        assert!(matches!(parse_err("p,q\np\nq\n0,12\n"), ParseError::InvalidSymbol { line: 4, column: 3, .. }));
+        assert!(matches!(parse_err("p,q\np\nq\n0\np,q,0;q,x,0"), ParseError::UnknownState { line: 5, column: 9, .. }));
+        assert!(matches!(parse_err("p,q\np\nq\n0\np,q,0;p,q,1"), ParseError::SymbolNotInAlphabet { symbol: '1', line: 5, column: 11 }));
+        assert!(matches!(parse_err("p,q\np\nq\n0\np,q,0;p,q"), ParseError::MalformedTransition { line: 5, column: 7, .. }));
+        assert!(matches!(parse_err("p,,q\np\nq\n0\n"), ParseError::EmptyField { line: 1, column: 3 }));
+        assert!(matches!(parse_err("p,q\np\nq\nz-a\n"), ParseError::InvalidSymbol { line: 4, column: 1, .. }));
+        assert!(matches!(parse_err("p,q\np\nq\n[:letter:]\n"), ParseError::InvalidSymbol { line: 4, column: 1, .. }));
+        assert!(matches!(parse_err("p,q\np\nq\n[^a]\n"), ParseError::InvalidSymbol { line: 4, column: 1, .. }));
+        assert!(matches!(parse_err("p,q\np\nq\na-z\np,q,[:alpha:]"), ParseError::SymbolNotInAlphabet { symbol: 'A', line: 5, column: 5 }));
+        assert!(matches!(parse_err("p,q\np\nq\na\np,q,a\nextra line\n"), ParseError::UnexpectedLine { line: 6, column: 1 }));
+        assert!(matches!(parse_err("p,q\np\nq\na\np,q,a\n\n  q,q,a\n"), ParseError::UnexpectedLine { line: 7, column: 3 }));
+        assert!(parse_fa_str("p,q\np\nq\na\np,q,a\n\n \n").is_ok());
+    }
+}
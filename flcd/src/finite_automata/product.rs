@@ -0,0 +1,225 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use crate::finite_automata::fa::{group_transitions, FiniteAutomaton};
+use crate::finite_automata::symbol_set::SymbolSet;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProductOperation {
+    Intersection,
+    Union,
+    Difference,
+    SymmetricDifference
+}
+
+impl ProductOperation {
+    fn accepts(&self, left: bool, right: bool) -> bool {
+        match self {
+            ProductOperation::Intersection => left && right,
+            ProductOperation::Union => left || right,
+            ProductOperation::Difference => left && !right,
+            ProductOperation::SymmetricDifference => left != right
+        }
+    }
+
+    /// Whether a pair whose sides are (still) alive can lead to an accepted word.
+    fn keeps(&self, left: bool, right: bool) -> bool {
+        match self {
+            ProductOperation::Intersection => left && right,
+            ProductOperation::Union | ProductOperation::SymmetricDifference => left || right,
+            ProductOperation::Difference => left
+        }
+    }
+}
+
+/// A DFA as a lookup table, with missing transitions leading nowhere.
+struct Table {
+    start: String,
+    finals: HashSet<String>,
+    delta: HashMap<(String, char), String>
+}
+
+impl Table {
+    fn new(fa: &FiniteAutomaton) -> Self {
+        let dfa = fa.to_dfa();
+        let finals = dfa.nodes.iter()
+            .filter(|node| node.is_final_state)
+            .map(|node| node.id.clone())
+            .collect();
+        let delta = dfa.transitions()
+            .into_iter()
+            .map(|(source, destination, symbol)| ((source, symbol), destination))
+            .collect();
+        let start = dfa.node(dfa.start).id.clone();
+
+        Table {
+            start,
+            finals,
+            delta
+        }
+    }
+
+    fn next(&self, state: &Option<String>, symbol: char) -> Option<String> {
+        state.as_ref().and_then(|state| self.delta.get(&(state.clone(), symbol)).cloned())
+    }
+
+    fn is_final(&self, state: &Option<String>) -> bool {
+        state.as_ref().is_some_and(|state| self.finals.contains(state))
+    }
+}
+
+type Pair = (Option<String>, Option<String>);
+
+/// Names `pair` after its sides, appending `'` while the name is taken by another pair.
+fn pair_id((left, right): &Pair, used: &HashSet<String>) -> String {
+    let mut id = format!("({}|{})", left.as_deref().unwrap_or("∅"), right.as_deref().unwrap_or("∅"));
+    while used.contains(&id) {
+        id.push('\'');
+    }
+
+    id
+}
+
+impl FiniteAutomaton {
+    /// Combines `self` and `other` with the product construction over the union of their
+    /// alphabets. States are named `(p|q)`, with `∅` standing for a side that has no transition
+    /// and `'` appended when two pairs would get the same name.
+    pub fn product(&self, other: &FiniteAutomaton, operation: ProductOperation) -> FiniteAutomaton {
+        let left = Table::new(self);
+        let right = Table::new(other);
+
+        let mut alphabet: Vec<char> = self.alphabet.union(&other.alphabet).cloned().collect();
+        alphabet.sort();
+
+        let start: Pair = (Some(left.start.clone()), Some(right.start.clone()));
+        let start_id = pair_id(&start, &HashSet::new());
+        let mut ids = HashMap::from([(start.clone(), start_id.clone())]);
+        let mut used = HashSet::from([start_id.clone()]);
+        let mut queue = VecDeque::from([start]);
+        let mut states = Vec::new();
+        let mut final_states = HashSet::new();
+        let mut transitions = Vec::new();
+
+        while let Some((l, r)) = queue.pop_front() {
+            let id = ids[&(l.clone(), r.clone())].clone();
+            states.push(id.clone());
+            if operation.accepts(left.is_final(&l), right.is_final(&r)) {
+                final_states.insert(id.clone());
+            }
+
+            for &symbol in &alphabet {
+                let next = (left.next(&l, symbol), right.next(&r, symbol));
+                if !operation.keeps(next.0.is_some(), next.1.is_some()) {
+                    continue;
+                }
+
+                let next_id = match ids.get(&next) {
+                    Some(next_id) => next_id.clone(),
+                    None => {
+                        let next_id = pair_id(&next, &used);
+                        used.insert(next_id.clone());
+                        ids.insert(next.clone(), next_id.clone());
+                        queue.push_back(next);
+                        next_id
+                    }
+                };
+                transitions.push((id.clone(), next_id, SymbolSet::single(symbol)));
+            }
+        }
+
+        FiniteAutomaton::with_symbol_sets(states,
+                                          start_id,
+                                          final_states,
+                                          alphabet.into_iter().collect(),
+                                          group_transitions(transitions))
+    }
+
+    pub fn intersection(&self, other: &FiniteAutomaton) -> FiniteAutomaton {
+        self.product(other, ProductOperation::Intersection)
+    }
+
+    pub fn union(&self, other: &FiniteAutomaton) -> FiniteAutomaton {
+        self.product(other, ProductOperation::Union)
+    }
+
+    pub fn difference(&self, other: &FiniteAutomaton) -> FiniteAutomaton {
+        self.product(other, ProductOperation::Difference)
+    }
+
+    pub fn symmetric_difference(&self, other: &FiniteAutomaton) -> FiniteAutomaton {
+        self.product(other, ProductOperation::SymmetricDifference)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Words over {a, b} with an even number of a's, and words over {a, c} ending in a.
+    fn before_each() -> (FiniteAutomaton, FiniteAutomaton) {
+        let even_a = FiniteAutomaton::new(
+            vec!["e", "o"],
+            "e",
+            HashSet::from(["e"]),
+            HashSet::from(['a', 'b']),
+            vec![("e", "o", 'a'), ("o", "e", 'a'), ("e", "e", 'b'), ("o", "o", 'b')]
+        );
+        let ends_in_a = FiniteAutomaton::new(
+            vec!["p", "q"],
+            "p",
+            HashSet::from(["q"]),
+            HashSet::from(['a', 'c']),
+            vec![("p", "p", 'a'), ("p", "p", 'c'), ("p", "q", 'a')]
+        );
+
+        (even_a, ends_in_a)
+    }
+
+    fn accepted(fa: &FiniteAutomaton, labels: &[&str]) -> Vec<bool> {
+        labels.iter().map(|label| fa.check_match(label).unwrap()).collect()
+    }
+
+    const LABELS: [&str; 7] = ["", "a", "aa", "b", "ca", "aba", "ac"];
+
+    #[test]
+    fn test_intersection() {
+        let (even_a, ends_in_a) = before_each();
+        let fa = even_a.intersection(&ends_in_a);
+        assert!(fa.is_dfa());
+        assert_eq!(accepted(&fa, &LABELS), vec![false, false, true, false, false, false, false]);
+    }
+
+    #[test]
+    fn test_union() {
+        let (even_a, ends_in_a) = before_each();
+        let fa = even_a.union(&ends_in_a);
+        assert_eq!(accepted(&fa, &LABELS), vec![true, true, true, true, true, true, false]);
+    }
+
+    #[test]
+    fn test_difference() {
+        let (even_a, ends_in_a) = before_each();
+        assert_eq!(accepted(&even_a.difference(&ends_in_a), &LABELS), vec![true, false, false, true, false, true, false]);
+        assert_eq!(accepted(&ends_in_a.difference(&even_a), &LABELS), vec![false, true, false, false, true, false, false]);
+    }
+
+    #[test]
+    fn test_symmetric_difference() {
+        let (even_a, ends_in_a) = before_each();
+        let fa = even_a.symmetric_difference(&ends_in_a);
+        assert_eq!(accepted(&fa, &LABELS), vec![true, true, false, true, true, true, false]);
+    }
+
+    #[test]
+    fn test_pair_names_are_unique() {
+        // The right automaton has a real state named ∅, like the sink added by `complete`.
+        let left = FiniteAutomaton::new(vec!["p", "q"], "p", HashSet::from(["q"]), HashSet::from(['a', 'b']),
+                                        vec![("p", "q", 'a'), ("p", "q", 'b')]);
+        let right = FiniteAutomaton::new(vec!["r", "∅"], "r", HashSet::from(["∅"]), HashSet::from(['a']),
+                                         vec![("r", "∅", 'a')]);
+
+        let fa = left.union(&right);
+        let ids: HashSet<&str> = fa.nodes.iter().map(|node| node.id.as_str()).collect();
+        assert_eq!(ids, HashSet::from(["(p|r)", "(q|∅)", "(q|∅)'"]));
+        assert_eq!(accepted(&fa, &["a", "b", "ab"]), vec![true, true, false]);
+        assert!(fa.to_fa_string().is_ok());
+    }
+}
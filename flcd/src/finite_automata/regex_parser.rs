@@ -0,0 +1,262 @@
+use std::fmt::{Display, Formatter};
+use crate::finite_automata::fa::EPSILON;
+use crate::finite_automata::regular_expression::RegularExpression;
+use crate::finite_automata::symbol_set::SymbolSet;
+
+/// Characters that have to be escaped with `\` to stand for themselves.
+const META_CHARS: &str = "()|*+?[]\\.^${}";
+
+/// The symbols `.` and negated classes choose from: the Basic Multilingual Plane, without `ε`.
+/// Alphabets are sets of characters, so the rest of Unicode would make them too large.
+fn any_symbol() -> SymbolSet {
+    SymbolSet::range('\0', '\u{FFFF}').difference(&SymbolSet::single(EPSILON))
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RegexError {
+    UnexpectedChar { ch: char, column: usize },
+    UnexpectedEnd { column: usize },
+    UnclosedGroup { column: usize },
+    UnclosedClass { column: usize },
+    EmptyClass { column: usize },
+    EpsilonSymbol { column: usize },
+    InvalidRange { start: char, end: char, column: usize }
+}
+
+impl Display for RegexError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegexError::UnexpectedChar { ch, column } => write!(f, "{}: unexpected {}", column, ch),
+            RegexError::UnexpectedEnd { column } => write!(f, "{}: unexpected end of the expression", column),
+            RegexError::UnclosedGroup { column } => write!(f, "{}: group is never closed", column),
+            RegexError::UnclosedClass { column } => write!(f, "{}: character class is never closed", column),
+            RegexError::EmptyClass { column } => write!(f, "{}: empty character class", column),
+            RegexError::EpsilonSymbol { column } => write!(f, "{}: ε stands for the empty word and cannot be a symbol", column),
+            RegexError::InvalidRange { start, end, column } => write!(f, "{}: invalid range {}-{}", column, start, end)
+        }
+    }
+}
+
+impl std::error::Error for RegexError {}
+
+/// Recursive descent parser for concatenation, `|`, `*`, `+`, `?`, groups, character classes and
+/// `.`, which matches any symbol but a line break. `∅` stands for the empty language and `()` or
+/// `ε` for the empty word, so `ε` is rejected as a symbol, even escaped or in a class.
+struct Parser {
+    chars: Vec<char>,
+    position: usize
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).cloned()
+    }
+
+    fn column(&self) -> usize {
+        self.position + 1
+    }
+
+    fn next(&mut self) -> Result<char, RegexError> {
+        let ch = self.peek().ok_or(RegexError::UnexpectedEnd { column: self.column() })?;
+        self.position += 1;
+        Ok(ch)
+    }
+
+    fn parse_alternation(&mut self) -> Result<RegularExpression, RegexError> {
+        let mut res = self.parse_concat()?;
+        while self.peek() == Some('|') {
+            self.position += 1;
+            res = RegularExpression::alternation(res, self.parse_concat()?);
+        }
+
+        Ok(res)
+    }
+
+    fn parse_concat(&mut self) -> Result<RegularExpression, RegexError> {
+        let mut res = RegularExpression::Epsilon;
+        while let Some(ch) = self.peek() {
+            if ch == '|' || ch == ')' {
+                break;
+            }
+            res = RegularExpression::concat(res, self.parse_repetition()?);
+        }
+
+        Ok(res)
+    }
+
+    fn parse_repetition(&mut self) -> Result<RegularExpression, RegexError> {
+        let mut res = self.parse_atom()?;
+        while let Some(ch) = self.peek() {
+            res = match ch {
+                '*' => RegularExpression::star(res),
+                '+' => RegularExpression::plus(res),
+                '?' => RegularExpression::optional(res),
+                _ => break
+            };
+            self.position += 1;
+        }
+
+        Ok(res)
+    }
+
+    fn parse_atom(&mut self) -> Result<RegularExpression, RegexError> {
+        let column = self.column();
+        match self.next()? {
+            '(' => {
+                let res = self.parse_alternation()?;
+                if self.peek() != Some(')') {
+                    return Err(RegexError::UnclosedGroup { column });
+                }
+                self.position += 1;
+                Ok(res)
+            },
+            '[' => self.parse_class(column),
+            '.' => Ok(RegularExpression::symbols(&any_symbol().difference(&SymbolSet::single('\n')))),
+            '\\' => match self.next()? {
+                EPSILON => Err(RegexError::EpsilonSymbol { column }),
+                ch => Ok(RegularExpression::Symbol(ch))
+            },
+            '∅' => Ok(RegularExpression::Empty),
+            'ε' => Ok(RegularExpression::Epsilon),
+            ch if META_CHARS.contains(ch) => Err(RegexError::UnexpectedChar { ch, column }),
+            ch => Ok(RegularExpression::Symbol(ch))
+        }
+    }
+
+    fn parse_class_char(&mut self, column: usize) -> Result<char, RegexError> {
+        match self.next() {
+            Ok('\\') => self.next().map_err(|_| RegexError::UnclosedClass { column }),
+            Ok(ch) => Ok(ch),
+            Err(_) => Err(RegexError::UnclosedClass { column })
+        }
+    }
+
+    fn parse_class(&mut self, column: usize) -> Result<RegularExpression, RegexError> {
+        let is_negated = self.peek() == Some('^');
+        if is_negated {
+            self.position += 1;
+        }
+
+        let mut res = SymbolSet::new();
+        loop {
+            match self.peek() {
+                None => return Err(RegexError::UnclosedClass { column }),
+                Some(']') => {
+                    self.position += 1;
+                    break;
+                },
+                Some(_) => {}
+            }
+
+            let range_column = self.column();
+            let start = self.parse_class_char(column)?;
+            let is_range = self.peek() == Some('-') && self.chars.get(self.position + 1).is_some_and(|ch| *ch != ']');
+            let end = if is_range {
+                self.position += 1;
+                self.parse_class_char(column)?
+            } else {
+                start
+            };
+
+            if end < start {
+                return Err(RegexError::InvalidRange { start, end, column: range_column });
+            }
+            if (start..=end).contains(&EPSILON) {
+                return Err(RegexError::EpsilonSymbol { column: range_column });
+            }
+
+            res = res.union(&SymbolSet::range(start, end));
+        }
+
+        if res.is_empty() {
+            return Err(RegexError::EmptyClass { column });
+        }
+        if is_negated {
+            res = any_symbol().difference(&res);
+        }
+
+        Ok(RegularExpression::symbols(&res))
+    }
+}
+
+pub fn parse_regex(pattern: &str) -> Result<RegularExpression, RegexError> {
+    let mut parser = Parser {
+        chars: pattern.chars().collect(),
+        position: 0
+    };
+
+    let res = parser.parse_alternation()?;
+    match parser.peek() {
+        Some(ch) => Err(RegexError::UnexpectedChar { ch, column: parser.column() }),
+        None => Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_regex() {
+        for pattern in ["0|[+\\-]?[1-9][0-9]*", "[A-Z_a-z]+", "(c|ab)*", "ab?", "∅", "()", "10*10*"] {
+            assert_eq!(parse_regex(pattern).unwrap().to_string(), pattern);
+        }
+
+        assert_eq!(parse_regex("a|b|c").unwrap().to_string(), "[a-c]");
+        assert_eq!(parse_regex("(a)(b)").unwrap().to_string(), "ab");
+        assert_eq!(parse_regex("\\.\\*").unwrap().to_string(), "\\.\\*");
+        assert_eq!(parse_regex("aε|ε").unwrap().to_string(), "a?");
+    }
+
+    #[test]
+    fn test_literal_empty_symbols() {
+        use crate::finite_automata::equivalence::Equivalence;
+        use crate::finite_automata::fa::FiniteAutomaton;
+        use std::collections::HashSet;
+
+        for pattern in ["\\∅", "a\\∅*", "[∅a]"] {
+            let expression = parse_regex(pattern).unwrap();
+            assert_eq!(parse_regex(&expression.to_string()).unwrap(), expression, "{}", pattern);
+        }
+
+        // A transition on a literal ∅ survives the trip through a regular expression.
+        let fa = FiniteAutomaton::new(vec!["p", "q"], "p", HashSet::from(["q"]), HashSet::from(['∅', 'a']),
+                                      vec![("p", "q", '∅'), ("q", "q", 'a')]);
+        let regex = fa.to_regex();
+        assert_eq!(regex.to_string(), "\\∅a*");
+        let reparsed = FiniteAutomaton::from_regex(&regex.to_string()).unwrap();
+        assert_eq!(reparsed.equivalent(&fa), Equivalence::Equivalent);
+    }
+
+    #[test]
+    fn test_negated_classes() {
+        use crate::finite_automata::fa::FiniteAutomaton;
+
+        let fa = FiniteAutomaton::from_regex("\"[^\"\\\\]*\"").unwrap();
+        for label in ["\"\"", "\"a b\"", "\"ă\"", "\"\u{FFFD}\""] {
+            assert!(fa.accepts(label), "{}", label);
+        }
+        for label in ["\"a\"b\"", "\"\\\\\"", "\"ε\"", "\"😀\""] {
+            assert!(!fa.accepts(label), "{}", label);
+        }
+
+        let fa = FiniteAutomaton::from_regex("a.b").unwrap();
+        assert!(fa.accepts("a\tb") && fa.accepts("aăb"));
+        assert!(!fa.accepts("a\nb") && !fa.accepts("ab"));
+    }
+
+    #[test]
+    fn test_parse_regex_errors() {
+        assert_eq!(parse_regex("(ab"), Err(RegexError::UnclosedGroup { column: 1 }));
+        assert_eq!(parse_regex("ab)"), Err(RegexError::UnexpectedChar { ch: ')', column: 3 }));
+        assert_eq!(parse_regex("a|*"), Err(RegexError::UnexpectedChar { ch: '*', column: 3 }));
+        assert_eq!(parse_regex("[a-"), Err(RegexError::UnclosedClass { column: 1 }));
+        assert_eq!(parse_regex("x[]"), Err(RegexError::EmptyClass { column: 2 }));
+        assert_eq!(parse_regex("[^]"), Err(RegexError::EmptyClass { column: 1 }));
+        assert_eq!(parse_regex("a\\ε"), Err(RegexError::EpsilonSymbol { column: 2 }));
+        assert_eq!(parse_regex("[ab\\ε]"), Err(RegexError::EpsilonSymbol { column: 4 }));
+        assert_eq!(parse_regex("[α-ω]"), Err(RegexError::EpsilonSymbol { column: 2 }));
+        assert_eq!(parse_regex("[z-a]"), Err(RegexError::InvalidRange { start: 'z', end: 'a', column: 2 }));
+        assert_eq!(parse_regex("a\\"), Err(RegexError::UnexpectedEnd { column: 3 }));
+    }
+}
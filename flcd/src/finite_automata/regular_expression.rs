@@ -0,0 +1,311 @@
+use std::fmt::{Display, Formatter};
+use crate::finite_automata::symbol_set::SymbolSet;
+
+const SPECIAL_CHARS: &str = "\\.+*?()|[]{}^$#&-~∅ε";
+const CLASS_SPECIAL_CHARS: &str = "\\[]^-&~";
+
+/// A regular expression tree. The constructors below simplify as they build, so trees built
+/// through them never contain `Empty` or `Epsilon` below the root.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RegularExpression {
+    Empty,
+    Epsilon,
+    Symbol(char),
+    Concat(Vec<RegularExpression>),
+    Alternation(Vec<RegularExpression>),
+    Star(Box<RegularExpression>),
+    Plus(Box<RegularExpression>),
+    Optional(Box<RegularExpression>)
+}
+
+use RegularExpression::*;
+
+impl RegularExpression {
+    pub fn is_nullable(&self) -> bool {
+        match self {
+            Empty | Symbol(_) => false,
+            Epsilon | Star(_) | Optional(_) => true,
+            Concat(items) => items.iter().all(RegularExpression::is_nullable),
+            Alternation(items) => items.iter().any(RegularExpression::is_nullable),
+            Plus(inner) => inner.is_nullable()
+        }
+    }
+
+    pub fn concat(left: RegularExpression, right: RegularExpression) -> RegularExpression {
+        let mut items: Vec<RegularExpression> = Vec::new();
+        for item in [left, right] {
+            match item {
+                Empty => return Empty,
+                Epsilon => {},
+                Concat(inner) => items.extend(inner),
+                other => items.push(other)
+            }
+        }
+
+        // x x* and x* x are both x+, and x* x* is x*.
+        let mut merged: Vec<RegularExpression> = Vec::new();
+        for item in items {
+            let replacement = match (merged.last(), &item) {
+                (Some(last), Star(inner)) if **inner == *last => Some(Plus(inner.clone())),
+                (Some(Star(inner)), _) if **inner == item => Some(Plus(inner.clone())),
+                (Some(last @ Star(_)), _) if *last == item => Some(item.clone()),
+                _ => None
+            };
+
+            match replacement {
+                Some(replacement) => *merged.last_mut().unwrap() = replacement,
+                None => merged.push(item)
+            }
+        }
+
+        match merged.len() {
+            0 => Epsilon,
+            1 => merged.pop().unwrap(),
+            _ => Concat(merged)
+        }
+    }
+
+    /// Splits off the last factor of a concatenation.
+    fn split_last(&self) -> (RegularExpression, RegularExpression) {
+        match self {
+            Concat(items) => {
+                let prefix = items[..items.len() - 1].iter().cloned().fold(Epsilon, RegularExpression::concat);
+                (prefix, items[items.len() - 1].clone())
+            },
+            other => (Epsilon, other.clone())
+        }
+    }
+
+    /// Splits off the first factor of a concatenation.
+    fn split_first(&self) -> (RegularExpression, RegularExpression) {
+        match self {
+            Concat(items) => {
+                let suffix = items[1..].iter().cloned().fold(Epsilon, RegularExpression::concat);
+                (items[0].clone(), suffix)
+            },
+            other => (other.clone(), Epsilon)
+        }
+    }
+
+    pub fn alternation(left: RegularExpression, right: RegularExpression) -> RegularExpression {
+        // Factor out a common prefix or suffix: xy|xz is x(y|z) and yx|zx is (y|z)x.
+        if !matches!(left, Empty | Epsilon) && !matches!(right, Empty | Epsilon) && left != right {
+            let (left_first, left_rest) = left.split_first();
+            let (right_first, right_rest) = right.split_first();
+            if left_first == right_first {
+                return RegularExpression::concat(left_first, RegularExpression::alternation(left_rest, right_rest));
+            }
+
+            let (left_rest, left_last) = left.split_last();
+            let (right_rest, right_last) = right.split_last();
+            if left_last == right_last {
+                return RegularExpression::concat(RegularExpression::alternation(left_rest, right_rest), left_last);
+            }
+        }
+
+        let mut items: Vec<RegularExpression> = Vec::new();
+        let mut has_epsilon = false;
+        let mut pending = vec![left, right];
+        while let Some(item) = pending.pop() {
+            match item {
+                Empty => {},
+                Epsilon => has_epsilon = true,
+                Optional(inner) => {
+                    has_epsilon = true;
+                    pending.push(*inner);
+                },
+                Alternation(inner) => pending.extend(inner),
+                other => items.push(other)
+            }
+        }
+
+        items.sort();
+        items.dedup();
+
+        let res = match items.len() {
+            0 => return if has_epsilon { Epsilon } else { Empty },
+            1 => items.pop().unwrap(),
+            _ => Alternation(items)
+        };
+
+        match res {
+            Plus(inner) if has_epsilon => Star(inner),
+            res if has_epsilon && !res.is_nullable() => Optional(Box::new(res)),
+            res => res
+        }
+    }
+
+    /// The alternation of the symbols in `symbols`. Same as folding them with `alternation`, but
+    /// linear in the number of symbols, so large classes stay cheap to build.
+    pub fn symbols(symbols: &SymbolSet) -> RegularExpression {
+        let mut items: Vec<RegularExpression> = symbols.iter().map(Symbol).collect();
+        match items.len() {
+            0 => Empty,
+            1 => items.pop().unwrap(),
+            _ => Alternation(items)
+        }
+    }
+
+    pub fn star(inner: RegularExpression) -> RegularExpression {
+        match inner {
+            Empty | Epsilon => Epsilon,
+            Star(inner) | Plus(inner) | Optional(inner) => Star(inner),
+            other => Star(Box::new(other))
+        }
+    }
+
+    pub fn plus(inner: RegularExpression) -> RegularExpression {
+        let star = RegularExpression::star(inner.clone());
+        RegularExpression::concat(inner, star)
+    }
+
+    pub fn optional(inner: RegularExpression) -> RegularExpression {
+        RegularExpression::alternation(inner, Epsilon)
+    }
+
+    fn fmt_with_precedence(&self, f: &mut Formatter<'_>, precedence: u8) -> std::fmt::Result {
+        let own = match self {
+            Alternation(items) if items.iter().all(|item| matches!(item, Symbol(_))) => 3,
+            Alternation(_) => 0,
+            Concat(_) => 1,
+            Star(_) | Plus(_) | Optional(_) => 2,
+            _ => 3
+        };
+
+        if own < precedence {
+            write!(f, "(")?;
+        }
+
+        match self {
+            Empty => write!(f, "∅")?,
+            Epsilon => write!(f, "()")?,
+            Symbol(ch) => write_symbol(f, *ch)?,
+            Concat(items) => {
+                for item in items {
+                    item.fmt_with_precedence(f, 2)?;
+                }
+            },
+            Alternation(items) => {
+                let symbols: Vec<char> = items.iter()
+                    .filter_map(|item| match item { Symbol(ch) => Some(*ch), _ => None })
+                    .collect();
+                let mut first = true;
+                if symbols.len() > 1 {
+                    write_class(f, &symbols)?;
+                    first = false;
+                }
+
+                for item in items {
+                    if symbols.len() > 1 && matches!(item, Symbol(_)) {
+                        continue;
+                    }
+                    if !first {
+                        write!(f, "|")?;
+                    }
+                    item.fmt_with_precedence(f, 1)?;
+                    first = false;
+                }
+            },
+            Star(inner) => {
+                inner.fmt_with_precedence(f, 3)?;
+                write!(f, "*")?;
+            },
+            Plus(inner) => {
+                inner.fmt_with_precedence(f, 3)?;
+                write!(f, "+")?;
+            },
+            Optional(inner) => {
+                inner.fmt_with_precedence(f, 3)?;
+                write!(f, "?")?;
+            }
+        }
+
+        if own < precedence {
+            write!(f, ")")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn write_symbol(f: &mut Formatter<'_>, ch: char) -> std::fmt::Result {
+    if SPECIAL_CHARS.contains(ch) {
+        write!(f, "\\")?;
+    }
+    write!(f, "{}", ch)
+}
+
+fn write_class_symbol(f: &mut Formatter<'_>, ch: char) -> std::fmt::Result {
+    if CLASS_SPECIAL_CHARS.contains(ch) {
+        write!(f, "\\")?;
+    }
+    write!(f, "{}", ch)
+}
+
+/// Writes sorted `symbols` as a character class, folding runs of three or more into ranges.
+fn write_class(f: &mut Formatter<'_>, symbols: &[char]) -> std::fmt::Result {
+    write!(f, "[")?;
+    let mut idx = 0;
+    while idx < symbols.len() {
+        let mut end = idx;
+        while end + 1 < symbols.len() && symbols[end + 1] as u32 == symbols[end] as u32 + 1 {
+            end += 1;
+        }
+
+        if end - idx >= 2 {
+            write_class_symbol(f, symbols[idx])?;
+            write!(f, "-")?;
+            write_class_symbol(f, symbols[end])?;
+        } else {
+            for ch in &symbols[idx..=end] {
+                write_class_symbol(f, *ch)?;
+            }
+        }
+        idx = end + 1;
+    }
+    write!(f, "]")
+}
+
+impl Display for RegularExpression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with_precedence(f, 0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn word(word: &str) -> RegularExpression {
+        word.chars().map(Symbol).fold(Epsilon, RegularExpression::concat)
+    }
+
+    #[test]
+    fn test_simplification() {
+        assert_eq!(RegularExpression::concat(Symbol('a'), Empty), Empty);
+        assert_eq!(RegularExpression::concat(Epsilon, Symbol('a')), Symbol('a'));
+        assert_eq!(RegularExpression::alternation(Symbol('a'), Empty), Symbol('a'));
+        assert_eq!(RegularExpression::alternation(Symbol('a'), Symbol('a')), Symbol('a'));
+        assert_eq!(RegularExpression::star(RegularExpression::star(Symbol('a'))), Star(Box::new(Symbol('a'))));
+        assert_eq!(RegularExpression::star(Epsilon), Epsilon);
+        assert_eq!(RegularExpression::concat(Symbol('a'), RegularExpression::star(Symbol('a'))), Plus(Box::new(Symbol('a'))));
+        assert_eq!(RegularExpression::alternation(Epsilon, RegularExpression::star(Symbol('a'))), Star(Box::new(Symbol('a'))));
+    }
+
+    #[test]
+    fn test_display() {
+        let digits = "0123456789".chars().map(Symbol).fold(Empty, RegularExpression::alternation);
+        let sign = RegularExpression::optional(RegularExpression::alternation(Symbol('+'), Symbol('-')));
+        let number = RegularExpression::concat(sign, RegularExpression::plus(digits));
+        assert_eq!(number.to_string(), "[+\\-]?[0-9]+");
+
+        let alternation = RegularExpression::alternation(word("ab"), Symbol('c'));
+        assert_eq!(RegularExpression::star(alternation).to_string(), "(c|ab)*");
+        assert_eq!(RegularExpression::alternation(word("ab"), word("cb")).to_string(), "[ac]b");
+        assert_eq!(RegularExpression::alternation(word("ab"), word("a")).to_string(), "ab?");
+        assert_eq!(RegularExpression::optional(word("ab")).to_string(), "(ab)?");
+        assert_eq!(Empty.to_string(), "∅");
+        assert_eq!(Epsilon.to_string(), "()");
+        assert_eq!(RegularExpression::concat(Symbol('∅'), Symbol('ε')).to_string(), "\\∅\\ε");
+    }
+}
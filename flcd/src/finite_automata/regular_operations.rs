@@ -0,0 +1,182 @@
+use std::collections::HashSet;
+use crate::finite_automata::fa::{EPSILON, FiniteAutomaton};
+use crate::finite_automata::symbol_set::SymbolSet;
+
+/// `name`, with `'` appended until it is not in `used`.
+fn fresh_name(name: &str, used: &HashSet<String>) -> String {
+    let mut res = name.to_owned();
+    while used.contains(&res) {
+        res.push('\'');
+    }
+
+    res
+}
+
+/// The pieces of an automaton under construction, named by state.
+struct Parts {
+    states: Vec<String>,
+    final_states: HashSet<String>,
+    transitions: Vec<(String, String, SymbolSet)>
+}
+
+impl Parts {
+    /// The states and transitions of `fa`, with the states whose names are in `used` renamed
+    /// by `fresh_name`. Returns the parts and the new name of the start state.
+    fn of(fa: &FiniteAutomaton, used: &HashSet<String>) -> (Parts, String) {
+        let mut taken: HashSet<String> = used.iter().cloned().chain(fa.nodes.iter().map(|node| node.id.clone())).collect();
+        let mut names = Vec::new();
+        for node in &fa.nodes {
+            if used.contains(&node.id) {
+                let name = fresh_name(&node.id, &taken);
+                taken.insert(name.clone());
+                names.push(name);
+            } else {
+                names.push(node.id.clone());
+            }
+        }
+
+        let final_states = fa.state_ids()
+            .filter(|state| fa.node(*state).is_final_state)
+            .map(|state| names[state.0].clone())
+            .collect();
+        let transitions = fa.state_ids()
+            .flat_map(|state| fa.node(state).transitions.iter().map(move |edge| (state, edge)))
+            .map(|(state, edge)| (names[state.0].clone(), names[edge.destination.0].clone(), edge.symbols.clone()))
+            .collect();
+
+        let start = names[fa.start.0].clone();
+        (Parts { states: names, final_states, transitions }, start)
+    }
+
+    fn add_epsilon(&mut self, source: &str, destination: &str) {
+        self.transitions.push((source.to_owned(), destination.to_owned(), SymbolSet::single(EPSILON)));
+    }
+
+    /// Adds a state named after `name` that does not clash with the existing ones.
+    fn add_state(&mut self, name: &str) -> String {
+        let used = self.states.iter().cloned().collect();
+        let res = fresh_name(name, &used);
+        self.states.push(res.clone());
+        res
+    }
+
+    fn build(self, start: String, alphabet: HashSet<char>) -> FiniteAutomaton {
+        FiniteAutomaton::with_symbol_sets(self.states, start, self.final_states, alphabet, self.transitions)
+    }
+}
+
+impl FiniteAutomaton {
+    /// Accepts the words made of a word of `self` followed by a word of `other`. States of
+    /// `other` whose names are already used get a `'` appended.
+    pub fn concatenation(&self, other: &FiniteAutomaton) -> FiniteAutomaton {
+        let (mut res, start) = Parts::of(self, &HashSet::new());
+        let (right, right_start) = Parts::of(other, &res.states.iter().cloned().collect());
+
+        for state in &res.final_states.clone() {
+            res.add_epsilon(state, &right_start);
+        }
+        res.states.extend(right.states);
+        res.transitions.extend(right.transitions);
+        res.final_states = right.final_states;
+
+        res.build(start, self.alphabet.union(&other.alphabet).cloned().collect())
+    }
+
+    /// Accepts any number of words of `self` in a row, including none. A new final start state
+    /// `s` is added.
+    pub fn star(&self) -> FiniteAutomaton {
+        let (mut res, old_start) = Parts::of(self, &HashSet::new());
+        let start = res.add_state("s");
+        res.add_epsilon(&start, &old_start);
+        for state in &res.final_states.clone() {
+            res.add_epsilon(state, &start);
+        }
+        res.final_states.insert(start.clone());
+
+        res.build(start, self.alphabet.clone())
+    }
+
+    /// Accepts one or more words of `self` in a row.
+    pub fn plus(&self) -> FiniteAutomaton {
+        let (mut res, start) = Parts::of(self, &HashSet::new());
+        for state in &res.final_states.clone() {
+            res.add_epsilon(state, &start);
+        }
+
+        res.build(start, self.alphabet.clone())
+    }
+
+    /// Accepts the words of `self` and the empty word. A new final start state `s` is added.
+    pub fn optional(&self) -> FiniteAutomaton {
+        let (mut res, old_start) = Parts::of(self, &HashSet::new());
+        let start = res.add_state("s");
+        res.add_epsilon(&start, &old_start);
+        res.final_states.insert(start.clone());
+
+        res.build(start, self.alphabet.clone())
+    }
+
+    /// Accepts the words of `self` read backwards. Every transition is reversed, the old start
+    /// state becomes the only final state and a new start state `s` leads to the old final ones.
+    pub fn reversal(&self) -> FiniteAutomaton {
+        let (mut res, old_start) = Parts::of(self, &HashSet::new());
+        res.transitions = res.transitions
+            .into_iter()
+            .map(|(source, destination, symbols)| (destination, source, symbols))
+            .collect();
+
+        let start = res.add_state("s");
+        for state in &res.final_states.clone() {
+            res.add_epsilon(&start, state);
+        }
+        res.final_states = HashSet::from([old_start]);
+
+        res.build(start, self.alphabet.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::finite_automata::equivalence::Equivalence;
+
+    fn regex(pattern: &str) -> FiniteAutomaton {
+        FiniteAutomaton::from_regex(pattern).unwrap()
+    }
+
+    fn assert_equivalent(fa: &FiniteAutomaton, pattern: &str) {
+        assert_eq!(fa.equivalent(&regex(pattern)), Equivalence::Equivalent, "{}", pattern);
+    }
+
+    #[test]
+    fn test_regular_operations() {
+        let ab = FiniteAutomaton::new(vec!["p", "q", "r"], "p", HashSet::from(["r"]), HashSet::from(['a', 'b']),
+                                      vec![("p", "q", 'a'), ("q", "r", 'b')]);
+        let c = FiniteAutomaton::new(vec!["p", "q"], "p", HashSet::from(["q"]), HashSet::from(['c']),
+                                     vec![("p", "q", 'c')]);
+
+        let concatenation = ab.concatenation(&c);
+        assert_eq!(concatenation.nodes.len(), 5);
+        assert!(concatenation.find_state("p'").is_some());
+        assert_equivalent(&concatenation, "abc");
+        assert_equivalent(&ab.star(), "(ab)*");
+        assert_equivalent(&ab.plus(), "(ab)+");
+        assert_equivalent(&ab.optional(), "(ab)?");
+        assert_equivalent(&ab.concatenation(&c.star()).reversal(), "c*ba");
+
+        let primed = FiniteAutomaton::new(vec!["p", "p'"], "p", HashSet::from(["p'"]), HashSet::from(['c']),
+                                          vec![("p", "p'", 'c')]);
+        let concatenation = ab.concatenation(&primed);
+        assert_eq!(concatenation.nodes.iter().map(|node| node.id.as_str()).collect::<Vec<_>>(), ["p", "q", "r", "p''", "p'"]);
+        assert_equivalent(&concatenation, "abc");
+    }
+
+    #[test]
+    fn test_signed_integer() {
+        let sign = regex("[+\\-]");
+        let unsigned = regex("0|[1-9][0-9]*");
+        let signed = sign.optional().concatenation(&unsigned);
+        assert_equivalent(&signed, "[+\\-]?(0|[1-9][0-9]*)");
+        assert!(signed.accepts("-120") && signed.accepts("0") && !signed.accepts("+"));
+    }
+}
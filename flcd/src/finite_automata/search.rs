@@ -0,0 +1,86 @@
+use std::fmt::{Display, Formatter};
+use crate::finite_automata::fa::FiniteAutomaton;
+
+/// A non-empty substring of a text accepted by an automaton. `start` and `end` are byte offsets,
+/// `line` and `column` are counted from 1, with columns counted in characters.
+#[derive(Debug, PartialEq)]
+pub struct Match {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+    pub text: String
+}
+
+impl Display for Match {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{} (bytes {}..{}): {}", self.line, self.column, self.start, self.end, self.text)
+    }
+}
+
+impl FiniteAutomaton {
+    /// Every non-empty substring of `text` accepted by the automaton, scanning left to right and
+    /// taking the longest match at each position. Without `overlapping`, the scan resumes after
+    /// the end of each match; with it, every position where a match starts is reported, so
+    /// `max` matched by an identifier automaton also yields `ax` and `x`.
+    pub fn find_matches(&self, text: &str, overlapping: bool) -> Vec<Match> {
+        let compiled = self.as_dfa().compile().expect("the subset construction builds a DFA");
+
+        let mut res = Vec::new();
+        let mut line = 1;
+        let mut column = 1;
+        let mut skip_until = 0;
+        for (start, ch) in text.char_indices() {
+            if start >= skip_until {
+                if let Some(length) = compiled.longest_match(text, start).filter(|length| *length > 0) {
+                    let end = start + length;
+                    res.push(Match { start, end, line, column, text: text[start..end].to_owned() });
+                    if !overlapping {
+                        skip_until = end;
+                    }
+                }
+            }
+
+            if ch == '\n' {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+        }
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn positions(matches: &[Match]) -> Vec<(usize, usize, &str)> {
+        matches.iter().map(|found| (found.line, found.column, found.text.as_str())).collect()
+    }
+
+    #[test]
+    fn test_find_matches() {
+        let ids = FiniteAutomaton::from_regex("[a-z][0-9a-z]*").unwrap();
+        let text = "let max = 0;\nif (x2 > max)\n  ă = b";
+
+        let matches = ids.find_matches(text, false);
+        assert_eq!(positions(&matches),
+                   [(1, 1, "let"), (1, 5, "max"), (2, 1, "if"), (2, 5, "x2"), (2, 10, "max"), (3, 7, "b")]);
+        assert_eq!((matches[5].start, matches[5].end), (34, 35));
+        assert_eq!(matches[1].to_string(), "1:5 (bytes 4..7): max");
+
+        let matches = ids.find_matches("max x", true);
+        assert_eq!(positions(&matches), [(1, 1, "max"), (1, 2, "ax"), (1, 3, "x"), (1, 5, "x")]);
+    }
+
+    #[test]
+    fn test_find_matches_skips_empty_words() {
+        let fa = FiniteAutomaton::from_regex("(ab)*").unwrap();
+        let matches = fa.find_matches("aabab", false);
+        assert_eq!(positions(&matches), [(1, 2, "abab")]);
+        assert_eq!(positions(&fa.find_matches("aabab", true)), [(1, 2, "abab"), (1, 4, "ab")]);
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::BTreeMap;
+use crate::finite_automata::fa::FiniteAutomaton;
+use crate::finite_automata::regular_expression::RegularExpression;
+
+type Edges = BTreeMap<(usize, usize), RegularExpression>;
+
+fn add_edge(edges: &mut Edges, source: usize, destination: usize, label: RegularExpression) {
+    let current = edges.remove(&(source, destination)).unwrap_or(RegularExpression::Empty);
+    edges.insert((source, destination), RegularExpression::alternation(current, label));
+}
+
+impl FiniteAutomaton {
+    /// Builds an equivalent regular expression by state elimination. A new start and a new
+    /// final state are added, then the original states are removed one by one, cheapest
+    /// first, relabelling the bypassing edges with the regular expressions they stand for.
+    pub fn to_regular_expression(&self) -> RegularExpression {
+        let start = self.nodes.len();
+        let end = self.nodes.len() + 1;
+
+        let mut edges = Edges::new();
+        add_edge(&mut edges, start, self.start.0, RegularExpression::Epsilon);
+        for (idx, node) in self.nodes.iter().enumerate() {
+            if node.is_final_state {
+                add_edge(&mut edges, idx, end, RegularExpression::Epsilon);
+            }
+            for edge in &node.transitions {
+                let label = if edge.is_epsilon() {
+                    RegularExpression::Epsilon
+                } else {
+                    RegularExpression::symbols(&edge.symbols)
+                };
+                add_edge(&mut edges, idx, edge.destination.0, label);
+            }
+        }
+
+        let mut remaining: Vec<usize> = (0..self.nodes.len()).collect();
+        while !remaining.is_empty() {
+            let cost = |state: usize| {
+                let incoming = edges.keys().filter(|(s, d)| *d == state && *s != state).count();
+                let outgoing = edges.keys().filter(|(s, d)| *s == state && *d != state).count();
+                incoming * outgoing
+            };
+            let position = (0..remaining.len()).min_by_key(|position| cost(remaining[*position])).unwrap();
+            let state = remaining.remove(position);
+
+            let self_loop = edges.remove(&(state, state))
+                .map(RegularExpression::star)
+                .unwrap_or(RegularExpression::Epsilon);
+            let incoming: Vec<(usize, RegularExpression)> = edges.iter()
+                .filter(|((_, d), _)| *d == state)
+                .map(|((s, _), label)| (*s, label.clone()))
+                .collect();
+            let outgoing: Vec<(usize, RegularExpression)> = edges.iter()
+                .filter(|((s, _), _)| *s == state)
+                .map(|((_, d), label)| (*d, label.clone()))
+                .collect();
+            edges.retain(|(s, d), _| *s != state && *d != state);
+
+            for (source, into) in &incoming {
+                for (destination, out_of) in &outgoing {
+                    let bypass = RegularExpression::concat(
+                        RegularExpression::concat(into.clone(), self_loop.clone()),
+                        out_of.clone()
+                    );
+                    add_edge(&mut edges, *source, *destination, bypass);
+                }
+            }
+        }
+
+        edges.remove(&(start, end)).unwrap_or(RegularExpression::Empty)
+    }
+
+    pub fn to_regex(&self) -> String {
+        self.to_regular_expression().to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use super::*;
+    use crate::finite_automata::parser::parse_fa;
+
+    #[test]
+    fn test_to_regex() {
+        let fa = FiniteAutomaton::new(
+            vec!["p", "q", "r"],
+            "p",
+            HashSet::from(["r"]),
+            HashSet::from(['0', '1']),
+            vec![("p", "q", '1'), ("q", "q", '0'), ("q", "r", '1'), ("r", "r", '0')]
+        );
+        assert_eq!(fa.to_regex(), "10*10*");
+
+        let int_const = parse_fa("int.in").unwrap();
+        assert_eq!(int_const.to_regex(), "0|[+\\-]?[1-9][0-9]*");
+    }
+
+    #[test]
+    fn test_to_regex_special_cases() {
+        let empty = FiniteAutomaton::new(vec!["p"], "p", HashSet::new(), HashSet::from(['a']), vec![("p", "p", 'a')]);
+        assert_eq!(empty.to_regex(), "∅");
+
+        let epsilon = FiniteAutomaton::new(vec!["p"], "p", HashSet::from(["p"]), HashSet::from(['a']), Vec::new());
+        assert_eq!(epsilon.to_regex(), "()");
+
+        let star = FiniteAutomaton::new(vec!["p"], "p", HashSet::from(["p"]), HashSet::from(['a', 'b']),
+                                        vec![("p", "p", 'a'), ("p", "p", 'b')]);
+        assert_eq!(star.to_regex(), "[ab]*");
+    }
+}
+
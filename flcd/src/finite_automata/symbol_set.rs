@@ -0,0 +1,282 @@
+use std::fmt::{Display, Formatter};
+
+/// Characters that have to be escaped with `\` inside a bracketed set. `,` and `;` separate
+/// the fields of FA files.
+const SET_SPECIAL_CHARS: &str = "\\[]^-,;";
+
+/// The character classes that can be used in labels as `[:name:]`. They only cover ASCII.
+const CLASSES: [(&str, &[(char, char)]); 10] = [
+    ("alpha", &[('A', 'Z'), ('a', 'z')]),
+    ("digit", &[('0', '9')]),
+    ("alnum", &[('0', '9'), ('A', 'Z'), ('a', 'z')]),
+    ("upper", &[('A', 'Z')]),
+    ("lower", &[('a', 'z')]),
+    ("xdigit", &[('0', '9'), ('A', 'F'), ('a', 'f')]),
+    ("word", &[('0', '9'), ('A', 'Z'), ('_', '_'), ('a', 'z')]),
+    ("space", &[('\t', '\r'), (' ', ' ')]),
+    ("punct", &[('!', '/'), (':', '@'), ('[', '`'), ('{', '~')]),
+    ("print", &[(' ', '~')])
+];
+
+fn next_char(ch: char) -> Option<char> {
+    match ch {
+        '\u{D7FF}' => Some('\u{E000}'),
+        _ => char::from_u32(ch as u32 + 1)
+    }
+}
+
+fn previous_char(ch: char) -> Option<char> {
+    match ch {
+        '\u{E000}' => Some('\u{D7FF}'),
+        _ => (ch as u32).checked_sub(1).and_then(char::from_u32)
+    }
+}
+
+/// A set of symbols, stored as sorted, disjoint and non-adjacent inclusive ranges. This is how
+/// edges are labelled, so `a-z` is a single edge instead of 26.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SymbolSet {
+    ranges: Vec<(char, char)>
+}
+
+impl SymbolSet {
+    pub fn new() -> Self {
+        SymbolSet::default()
+    }
+
+    pub fn single(ch: char) -> Self {
+        SymbolSet { ranges: vec![(ch, ch)] }
+    }
+
+    /// The symbols from `start` to `end`, both included. Empty if `end` comes before `start`.
+    pub fn range(start: char, end: char) -> Self {
+        SymbolSet::from_ranges([(start, end)])
+    }
+
+    pub fn from_ranges<I: IntoIterator<Item = (char, char)>>(ranges: I) -> Self {
+        let mut sorted: Vec<(char, char)> = ranges.into_iter().filter(|(start, end)| start <= end).collect();
+        sorted.sort();
+
+        let mut res: Vec<(char, char)> = Vec::new();
+        for (start, end) in sorted {
+            match res.last_mut() {
+                Some((_, last_end)) if next_char(*last_end).is_none_or(|next| start <= next) => *last_end = (*last_end).max(end),
+                _ => res.push((start, end))
+            }
+        }
+
+        SymbolSet { ranges: res }
+    }
+
+    /// The class called `name`, as in `[:name:]`.
+    pub fn class(name: &str) -> Option<Self> {
+        CLASSES.iter()
+            .find(|(class_name, _)| *class_name == name)
+            .map(|(_, ranges)| SymbolSet::from_ranges(ranges.iter().cloned()))
+    }
+
+    pub fn ranges(&self) -> &[(char, char)] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        let surrogates = 0xE000 - 0xD800;
+        self.ranges.iter()
+            .map(|(start, end)| {
+                let len = *end as usize - *start as usize + 1;
+                if *start <= '\u{D7FF}' && *end >= '\u{E000}' { len - surrogates } else { len }
+            })
+            .sum()
+    }
+
+    pub fn contains(&self, ch: char) -> bool {
+        self.ranges
+            .binary_search_by(|(start, end)| {
+                if *end < ch {
+                    std::cmp::Ordering::Less
+                } else if *start > ch {
+                    std::cmp::Ordering::Greater
+                } else {
+                    std::cmp::Ordering::Equal
+                }
+            })
+            .is_ok()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = char> + '_ {
+        self.ranges.iter().flat_map(|(start, end)| *start..=*end)
+    }
+
+    pub fn union(&self, other: &SymbolSet) -> SymbolSet {
+        SymbolSet::from_ranges(self.ranges.iter().chain(&other.ranges).cloned())
+    }
+
+    pub fn intersection(&self, other: &SymbolSet) -> SymbolSet {
+        let mut res = Vec::new();
+        let (mut left, mut right) = (0, 0);
+        while left < self.ranges.len() && right < other.ranges.len() {
+            let (left_start, left_end) = self.ranges[left];
+            let (right_start, right_end) = other.ranges[right];
+            let start = left_start.max(right_start);
+            let end = left_end.min(right_end);
+            if start <= end {
+                res.push((start, end));
+            }
+
+            if left_end < right_end {
+                left += 1;
+            } else {
+                right += 1;
+            }
+        }
+
+        SymbolSet { ranges: res }
+    }
+
+    /// Every character that is not in `self`.
+    pub fn complement(&self) -> SymbolSet {
+        let mut res = Vec::new();
+        let mut start = Some('\0');
+        for (range_start, range_end) in &self.ranges {
+            if let Some(gap_start) = start {
+                if let Some(gap_end) = previous_char(*range_start).filter(|end| gap_start <= *end) {
+                    res.push((gap_start, gap_end));
+                }
+            }
+            start = next_char(*range_end);
+        }
+
+        if let Some(gap_start) = start {
+            res.push((gap_start, char::MAX));
+        }
+
+        SymbolSet { ranges: res }
+    }
+
+    pub fn difference(&self, other: &SymbolSet) -> SymbolSet {
+        self.intersection(&other.complement())
+    }
+
+    pub fn is_subset(&self, other: &SymbolSet) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    pub fn overlaps(&self, other: &SymbolSet) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// Splits the symbols of `sets` into sorted, disjoint ranges such that every set is a union
+    /// of some of them. Symbols in the same range are in exactly the same sets.
+    pub fn partition<'a, I: IntoIterator<Item = &'a SymbolSet>>(sets: I) -> Vec<SymbolSet> {
+        let mut ranges = Vec::new();
+        let mut cuts = Vec::new();
+        for set in sets {
+            for (start, end) in &set.ranges {
+                ranges.push((*start, *end));
+                cuts.push(*start);
+                cuts.extend(next_char(*end));
+            }
+        }
+        cuts.sort();
+        cuts.dedup();
+
+        let union = SymbolSet::from_ranges(ranges);
+        let ends = cuts.iter().skip(1).map(|cut| previous_char(*cut).unwrap()).chain([char::MAX]);
+        cuts.iter()
+            .zip(ends)
+            .filter(|(start, _)| union.contains(**start))
+            .map(|(start, end)| SymbolSet::range(*start, end))
+            .collect()
+    }
+}
+
+impl FromIterator<char> for SymbolSet {
+    fn from_iter<T: IntoIterator<Item = char>>(iter: T) -> Self {
+        SymbolSet::from_ranges(iter.into_iter().map(|ch| (ch, ch)))
+    }
+}
+
+/// Whether `ch` can be written on its own as a label, outside of a bracketed set.
+fn is_plain(ch: char) -> bool {
+    !ch.is_whitespace() && !",;\\".contains(ch)
+}
+
+fn write_set_char(f: &mut Formatter<'_>, ch: char) -> std::fmt::Result {
+    if SET_SPECIAL_CHARS.contains(ch) {
+        write!(f, "\\")?;
+    }
+    write!(f, "{}", ch)
+}
+
+/// Writes the set the way labels are written in FA files: `a`, `a-z`, `[ab]` or `[a-zA-Z_]`.
+impl Display for SymbolSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.ranges.as_slice() {
+            [(start, end)] if start == end && is_plain(*start) => write!(f, "{}", start),
+            [(start, end)] if is_plain(*start) && is_plain(*end) && next_char(*start) != Some(*end) =>
+                write!(f, "{}-{}", start, end),
+            ranges => {
+                write!(f, "[")?;
+                for (start, end) in ranges {
+                    write_set_char(f, *start)?;
+                    if next_char(*start) == Some(*end) {
+                        write_set_char(f, *end)?;
+                    } else if start != end {
+                        write!(f, "-")?;
+                        write_set_char(f, *end)?;
+                    }
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_set_operations() {
+        let letters = SymbolSet::from_ranges([('a', 'z'), ('A', 'Z')]);
+        let underscore = SymbolSet::single('_');
+        assert_eq!(letters.union(&underscore).len(), 53);
+        assert_eq!(SymbolSet::from_ranges([('a', 'c'), ('d', 'f')]), SymbolSet::range('a', 'f'));
+        assert_eq!(letters.intersection(&SymbolSet::range('X', 'c')), SymbolSet::from_ranges([('X', 'Z'), ('a', 'c')]));
+        assert_eq!(SymbolSet::class("alnum").unwrap().difference(&letters), SymbolSet::class("digit").unwrap());
+        assert!(letters.contains('q') && !letters.contains('_'));
+        assert!(SymbolSet::range('a', 'f').is_subset(&letters));
+        assert!(!letters.overlaps(&SymbolSet::class("digit").unwrap()));
+        assert_eq!(SymbolSet::new().complement().complement(), SymbolSet::new());
+        assert!(!SymbolSet::range('\u{D000}', '\u{E100}').complement().contains('\u{E000}'));
+        assert_eq!(SymbolSet::range('z', 'a'), SymbolSet::new());
+    }
+
+    #[test]
+    fn test_partition() {
+        let letters = SymbolSet::from_ranges([('a', 'z'), ('A', 'Z')]);
+        let classes = SymbolSet::partition([&letters, &SymbolSet::range('0', 'f'), &SymbolSet::single('x')]);
+        let expected = [('0', '@'), ('A', 'Z'), ('[', '`'), ('a', 'f'), ('g', 'w'), ('x', 'x'), ('y', 'z')];
+        assert_eq!(classes, expected.map(|(start, end)| SymbolSet::range(start, end)));
+
+        let classes = SymbolSet::partition([&SymbolSet::range('\u{D000}', char::MAX), &SymbolSet::single('\u{E000}')]);
+        assert_eq!(classes, [SymbolSet::range('\u{D000}', '\u{D7FF}'), SymbolSet::single('\u{E000}'),
+                             SymbolSet::range('\u{E001}', char::MAX)]);
+        assert!(SymbolSet::partition([]).is_empty());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(SymbolSet::single('-').to_string(), "-");
+        assert_eq!(SymbolSet::range('0', '9').to_string(), "0-9");
+        assert_eq!(SymbolSet::class("word").unwrap().to_string(), "[0-9A-Z_a-z]");
+        assert_eq!("+-".chars().collect::<SymbolSet>().to_string(), "[+\\-]");
+        assert_eq!(SymbolSet::single(',').to_string(), "[\\,]");
+        assert_eq!(SymbolSet::range('0', '1').to_string(), "[01]");
+        assert_eq!(SymbolSet::range(' ', '~').to_string(), "[ -~]");
+    }
+}
@@ -0,0 +1,105 @@
+use crate::finite_automata::fa::{EPSILON, FiniteAutomaton, StateId};
+use crate::finite_automata::symbol_set::SymbolSet;
+
+/// The states reached from each state by reading `symbol`, named and joined with commas, or `-`
+/// if there are none.
+fn column(fa: &FiniteAutomaton, symbol: char) -> Vec<String> {
+    fa.nodes.iter()
+        .map(|node| {
+            let mut destinations: Vec<StateId> = node.transitions.iter()
+                .filter(|edge| edge.symbols.contains(symbol))
+                .map(|edge| edge.destination)
+                .collect();
+            destinations.sort();
+            destinations.dedup();
+
+            if destinations.is_empty() {
+                "-".to_owned()
+            } else {
+                destinations.iter().map(|state| fa.node(*state).id.as_str()).collect::<Vec<_>>().join(",")
+            }
+        })
+        .collect()
+}
+
+impl FiniteAutomaton {
+    /// The transition function as a table with a row per state and a column per symbol. The start
+    /// state is marked with `->` and the final states with `*`. Symbols with the same column are
+    /// merged into one, labelled like a transition (`1-9`), and epsilon transitions get a column
+    /// of their own at the end.
+    pub fn to_table(&self) -> String {
+        let mut alphabet: Vec<char> = self.alphabet.iter().cloned().collect();
+        alphabet.sort();
+
+        let mut columns: Vec<(Vec<char>, Vec<String>)> = Vec::new();
+        for symbol in alphabet {
+            let cells = column(self, symbol);
+            match columns.iter_mut().find(|(_, other)| *other == cells) {
+                Some((symbols, _)) => symbols.push(symbol),
+                None => columns.push((vec![symbol], cells))
+            }
+        }
+        if self.has_epsilon_transitions() {
+            columns.push((vec![EPSILON], column(self, EPSILON)));
+        }
+
+        let mut rows = vec![[String::new()].into_iter()
+            .chain(columns.iter().map(|(symbols, _)| symbols.iter().cloned().collect::<SymbolSet>().to_string()))
+            .collect::<Vec<String>>()];
+        for state in self.state_ids() {
+            let node = self.node(state);
+            let start = if state == self.start { "->" } else { "  " };
+            let is_final = if node.is_final_state { "*" } else { " " };
+            rows.push([format!("{}{} {}", start, is_final, node.id)].into_iter()
+                .chain(columns.iter().map(|(_, cells)| cells[state.0].clone()))
+                .collect());
+        }
+
+        let widths: Vec<usize> = (0..rows[0].len())
+            .map(|idx| rows.iter().map(|row| row[idx].chars().count()).max().unwrap())
+            .collect();
+
+        let mut res = String::new();
+        for row in rows {
+            let cells: Vec<String> = row.iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{}{}", cell, " ".repeat(width - cell.chars().count())))
+                .collect();
+            res.push_str(cells.join(" | ").trim_end());
+            res.push('\n');
+        }
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use super::*;
+    use crate::finite_automata::parser::parse_fa;
+
+    #[test]
+    fn test_to_table() {
+        let fa = parse_fa("int.in").unwrap();
+
+        assert_eq!(fa.to_table(), [
+            "      | [+\\-] | 0 | 1-9",
+            "->  p | s     | z | n",
+            "  * z | -     | - | -",
+            "    s | -     | - | n",
+            "  * n | -     | n | n\n"
+        ].join("\n"));
+    }
+
+    #[test]
+    fn test_to_table_nfa() {
+        let fa = FiniteAutomaton::new(vec!["p", "q"], "p", HashSet::from(["q"]), HashSet::from(['a']),
+                                      vec![("p", "p", 'a'), ("p", "q", 'a'), ("q", "p", EPSILON)]);
+        assert_eq!(fa.to_table(), [
+            "      | a   | ε",
+            "->  p | p,q | -",
+            "  * q | -   | p\n"
+        ].join("\n"));
+    }
+}
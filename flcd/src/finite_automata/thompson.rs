@@ -0,0 +1,161 @@
+use std::collections::HashSet;
+use crate::finite_automata::fa::{EPSILON, FiniteAutomaton};
+use crate::finite_automata::regex_parser::{parse_regex, RegexError};
+use crate::finite_automata::regular_expression::RegularExpression;
+use crate::finite_automata::symbol_set::SymbolSet;
+
+/// Collects the states and transitions of a Thompson construction. States are numbered in
+/// creation order.
+#[derive(Default)]
+struct Builder {
+    states: usize,
+    alphabet: SymbolSet,
+    transitions: Vec<(usize, usize, SymbolSet)>
+}
+
+impl Builder {
+    fn new_state(&mut self) -> usize {
+        self.states += 1;
+        self.states - 1
+    }
+
+    fn add_epsilon(&mut self, source: usize, destination: usize) {
+        self.transitions.push((source, destination, SymbolSet::single(EPSILON)));
+    }
+
+    fn add_symbols(&mut self, source: usize, destination: usize, symbols: SymbolSet) {
+        self.alphabet = self.alphabet.union(&symbols);
+        self.transitions.push((source, destination, symbols));
+    }
+
+    /// Builds the fragment for `expression`, returning its start and final state.
+    fn build(&mut self, expression: &RegularExpression) -> (usize, usize) {
+        let start = self.new_state();
+        let end;
+        match expression {
+            RegularExpression::Empty => end = self.new_state(),
+            RegularExpression::Epsilon => {
+                end = self.new_state();
+                self.add_epsilon(start, end);
+            },
+            RegularExpression::Symbol(ch) => {
+                end = self.new_state();
+                self.add_symbols(start, end, SymbolSet::single(*ch));
+            },
+            RegularExpression::Concat(items) => {
+                let mut last = start;
+                for item in items {
+                    let (item_start, item_end) = self.build(item);
+                    self.add_epsilon(last, item_start);
+                    last = item_end;
+                }
+                end = last;
+            },
+            RegularExpression::Alternation(items) => {
+                // The symbols share a single transition labelled with all of them, so a class
+                // like [a-z] costs one edge instead of a fragment per letter.
+                let symbols: SymbolSet = items.iter()
+                    .filter_map(|item| match item { RegularExpression::Symbol(ch) => Some(*ch), _ => None })
+                    .collect();
+                let fragments: Vec<(usize, usize)> = items.iter()
+                    .filter(|item| !matches!(item, RegularExpression::Symbol(_)))
+                    .map(|item| self.build(item))
+                    .collect();
+                end = self.new_state();
+                if !symbols.is_empty() {
+                    self.add_symbols(start, end, symbols);
+                }
+                for (item_start, item_end) in fragments {
+                    self.add_epsilon(start, item_start);
+                    self.add_epsilon(item_end, end);
+                }
+            },
+            RegularExpression::Star(inner) | RegularExpression::Plus(inner) | RegularExpression::Optional(inner) => {
+                let (inner_start, inner_end) = self.build(inner);
+                end = self.new_state();
+                self.add_epsilon(start, inner_start);
+                self.add_epsilon(inner_end, end);
+                if !matches!(expression, RegularExpression::Plus(_)) {
+                    self.add_epsilon(start, end);
+                }
+                if !matches!(expression, RegularExpression::Optional(_)) {
+                    self.add_epsilon(inner_end, inner_start);
+                }
+            }
+        }
+
+        (start, end)
+    }
+}
+
+impl FiniteAutomaton {
+    /// Builds an epsilon-NFA for `expression` with the Thompson construction. The alphabet is
+    /// the set of symbols used in the expression.
+    pub fn from_regular_expression(expression: &RegularExpression) -> FiniteAutomaton {
+        let mut builder = Builder::default();
+        let (start, end) = builder.build(expression);
+
+        let states: Vec<String> = (0..builder.states).map(|state| state.to_string()).collect();
+        let transitions = builder.transitions
+            .into_iter()
+            .map(|(source, destination, symbols)| (states[source].clone(), states[destination].clone(), symbols))
+            .collect();
+
+        FiniteAutomaton::with_symbol_sets(states.clone(),
+                                          states[start].clone(),
+                                          HashSet::from([states[end].clone()]),
+                                          builder.alphabet.iter().collect(),
+                                          transitions)
+    }
+
+    pub fn from_regex(pattern: &str) -> Result<FiniteAutomaton, RegexError> {
+        Ok(FiniteAutomaton::from_regular_expression(&parse_regex(pattern)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::finite_automata::equivalence::Equivalence;
+
+    fn matches(pattern: &str, label: &str) -> bool {
+        FiniteAutomaton::from_regex(pattern).unwrap().to_dfa().check_match(label).unwrap()
+    }
+
+    #[test]
+    fn test_from_regex() {
+        assert!(matches("0|[+\\-]?[1-9][0-9]*", "-120"));
+        assert!(matches("0|[+\\-]?[1-9][0-9]*", "0"));
+        assert!(!matches("0|[+\\-]?[1-9][0-9]*", "012"));
+        assert!(matches("(ab|c)*", ""));
+        assert!(matches("(ab|c)*", "abcab"));
+        assert!(!matches("(ab|c)*", "aba"));
+        assert!(matches("a+b?", "aaab"));
+        assert!(!matches("a+b?", "b"));
+        assert!(!matches("∅", ""));
+        assert!(matches("()", ""));
+    }
+
+    #[test]
+    fn test_classes_are_single_transitions() {
+        let fa = FiniteAutomaton::from_regex("[^a]").unwrap();
+        assert_eq!(fa.symbol_set_transitions().len(), 1);
+        assert!(fa.alphabet.contains(&'\u{FFFF}') && !fa.alphabet.contains(&'\u{10000}'));
+        assert!(!fa.alphabet.contains(&'a') && !fa.alphabet.contains(&EPSILON));
+        assert!(fa.accepts("ă") && !fa.accepts("a"));
+    }
+
+    #[test]
+    fn test_regex_round_trip() {
+        let fa = FiniteAutomaton::new(
+            vec!["p", "q", "r"],
+            "p",
+            HashSet::from(["p", "r"]),
+            HashSet::from(['a', 'b']),
+            vec![("p", "q", 'a'), ("q", "q", 'b'), ("q", "r", 'a'), ("r", "p", 'b'), ("r", "q", 'a')]
+        );
+
+        let round_trip = FiniteAutomaton::from_regex(&fa.to_regex()).unwrap();
+        assert_eq!(fa.equivalent(&round_trip), Equivalence::Equivalent);
+    }
+}
@@ -0,0 +1,148 @@
+use std::fmt::{Display, Formatter};
+use crate::finite_automata::fa::{FiniteAutomaton, NotDFAError};
+
+#[derive(Debug, PartialEq)]
+pub struct TraceStep {
+    pub state: String,
+    pub symbol: char,
+    pub next_state: String
+}
+
+/// Why a label was rejected. `index` is the position of the offending symbol in the label.
+#[derive(Debug, PartialEq)]
+pub enum Rejection {
+    SymbolNotInAlphabet { symbol: char, index: usize },
+    NoTransition { state: String, symbol: char, index: usize },
+    NonFinalState { state: String }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Trace {
+    pub steps: Vec<TraceStep>,
+    pub rejection: Option<Rejection>
+}
+
+impl Trace {
+    pub fn is_accepted(&self) -> bool {
+        self.rejection.is_none()
+    }
+}
+
+impl Display for Trace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for step in &self.steps {
+            writeln!(f, "{} -{}-> {}", step.state, step.symbol, step.next_state)?;
+        }
+
+        match &self.rejection {
+            None => write!(f, "Accepted"),
+            Some(Rejection::SymbolNotInAlphabet { symbol, index }) =>
+                write!(f, "Rejected: symbol {} at index {} is not in the alphabet", symbol, index),
+            Some(Rejection::NoTransition { state, symbol, index }) =>
+                write!(f, "Rejected: no transition from {} on {} at index {}", state, symbol, index),
+            Some(Rejection::NonFinalState { state }) =>
+                write!(f, "Rejected: the input ended in {}, which is not a final state", state)
+        }
+    }
+}
+
+impl FiniteAutomaton {
+    /// Same as `check_match`, but records every step of the run and the reason for a rejection.
+    pub fn check_match_traced(&self, label: &str) -> Result<Trace, NotDFAError> {
+        let mut steps = Vec::new();
+        let mut node = self.node(self.start);
+        for (index, ch) in label.chars().enumerate() {
+            if node.transitions.iter().any(|edge| edge.is_epsilon()) {
+                return Err(NotDFAError);
+            }
+
+            if !self.alphabet.contains(&ch) {
+                let rejection = Rejection::SymbolNotInAlphabet { symbol: ch, index };
+                return Ok(Trace { steps, rejection: Some(rejection) });
+            }
+
+            let destinations: Vec<_> = node.transitions
+                .iter()
+                .filter(|edge| edge.symbols.contains(ch))
+                .map(|edge| edge.destination)
+                .collect();
+
+            let next_node = match destinations.len() {
+                0 => {
+                    let state = node.id.clone();
+                    let rejection = Rejection::NoTransition { state, symbol: ch, index };
+                    return Ok(Trace { steps, rejection: Some(rejection) });
+                },
+                1 => self.node(destinations[0]),
+                _ => return Err(NotDFAError)
+            };
+
+            steps.push(TraceStep {
+                state: node.id.clone(),
+                symbol: ch,
+                next_state: next_node.id.clone()
+            });
+            node = next_node;
+        }
+
+        if node.transitions.iter().any(|edge| edge.is_epsilon()) {
+            return Err(NotDFAError);
+        }
+
+        let rejection = if node.is_final_state {
+            None
+        } else {
+            Some(Rejection::NonFinalState { state: node.id.clone() })
+        };
+
+        Ok(Trace { steps, rejection })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use super::*;
+
+    fn before_each() -> FiniteAutomaton {
+        FiniteAutomaton::new(
+            vec!["p", "q", "r"],
+            "p",
+            HashSet::from(["r"]),
+            HashSet::from(['0', '1']),
+            vec![("p", "q", '1'), ("q", "q", '0'), ("q", "r", '1'), ("r", "r", '0')]
+        )
+    }
+
+    fn step(state: &str, symbol: char, next_state: &str) -> TraceStep {
+        TraceStep {
+            state: state.to_owned(),
+            symbol,
+            next_state: next_state.to_owned()
+        }
+    }
+
+    #[test]
+    fn test_accepted_trace() {
+        let trace = before_each().check_match_traced("101").unwrap();
+        assert!(trace.is_accepted());
+        assert_eq!(trace.steps, vec![step("p", '1', "q"), step("q", '0', "q"), step("q", '1', "r")]);
+        assert_eq!(trace.to_string(), "p -1-> q\nq -0-> q\nq -1-> r\nAccepted");
+    }
+
+    #[test]
+    fn test_rejected_traces() {
+        let fa = before_each();
+
+        let trace = fa.check_match_traced("1011").unwrap();
+        assert_eq!(trace.steps.len(), 3);
+        assert_eq!(trace.rejection, Some(Rejection::NoTransition { state: "r".to_owned(), symbol: '1', index: 3 }));
+
+        let trace = fa.check_match_traced("10").unwrap();
+        assert_eq!(trace.rejection, Some(Rejection::NonFinalState { state: "q".to_owned() }));
+
+        let trace = fa.check_match_traced("1a").unwrap();
+        assert_eq!(trace.steps, vec![step("p", '1', "q")]);
+        assert_eq!(trace.rejection, Some(Rejection::SymbolNotInAlphabet { symbol: 'a', index: 1 }));
+    }
+}
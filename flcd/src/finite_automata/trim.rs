@@ -0,0 +1,130 @@
+use std::collections::{HashSet, VecDeque};
+use crate::finite_automata::fa::{FiniteAutomaton, StateId};
+
+/// The states removed by `trim`.
+#[derive(Debug, PartialEq)]
+pub struct TrimReport {
+    pub unreachable: Vec<String>,
+    pub dead: Vec<String>
+}
+
+impl TrimReport {
+    pub fn is_empty(&self) -> bool {
+        self.unreachable.is_empty() && self.dead.is_empty()
+    }
+}
+
+/// The states reachable from `starts` by following `next`.
+fn reachable<F: Fn(StateId) -> Vec<StateId>>(size: usize, starts: &[StateId], next: F) -> Vec<bool> {
+    let mut res = vec![false; size];
+    let mut queue: VecDeque<StateId> = starts.iter().cloned().collect();
+    for state in starts {
+        res[state.0] = true;
+    }
+
+    while let Some(state) = queue.pop_front() {
+        for neighbour in next(state) {
+            if !res[neighbour.0] {
+                res[neighbour.0] = true;
+                queue.push_back(neighbour);
+            }
+        }
+    }
+
+    res
+}
+
+impl FiniteAutomaton {
+    /// Removes the states that cannot be reached from the start state and the states from which
+    /// no final state can be reached, along with their transitions. The start state is always
+    /// kept, so an automaton with an empty language is trimmed down to it, without any
+    /// transitions. Unlike `minimize`, this works on nondeterministic automata and keeps the
+    /// remaining states as they are.
+    pub fn trim(&self) -> (FiniteAutomaton, TrimReport) {
+        let mut predecessors = vec![Vec::new(); self.nodes.len()];
+        for (source, node) in self.nodes.iter().enumerate() {
+            for edge in &node.transitions {
+                predecessors[edge.destination.0].push(StateId(source));
+            }
+        }
+
+        let is_reachable = reachable(self.nodes.len(), &[self.start], |state| {
+            self.node(state).transitions.iter().map(|edge| edge.destination).collect()
+        });
+        let finals: Vec<StateId> = self.state_ids().filter(|state| self.node(*state).is_final_state).collect();
+        let is_alive = reachable(self.nodes.len(), &finals, |state| predecessors[state.0].clone());
+
+        let mut report = TrimReport { unreachable: Vec::new(), dead: Vec::new() };
+        let mut kept = HashSet::new();
+        for state in self.state_ids() {
+            let id = self.node(state).id.as_str();
+            if !is_reachable[state.0] {
+                report.unreachable.push(id.to_owned());
+            } else if !is_alive[state.0] && state != self.start {
+                report.dead.push(id.to_owned());
+            } else {
+                kept.insert(id);
+            }
+        }
+
+        let states: Vec<&str> = self.nodes.iter().map(|node| node.id.as_str()).filter(|id| kept.contains(id)).collect();
+        let final_states = self.nodes.iter()
+            .filter(|node| node.is_final_state && kept.contains(node.id.as_str()))
+            .map(|node| node.id.as_str())
+            .collect();
+        // A dead start state is kept without its transitions, which lead nowhere useful.
+        let transitions = self.state_ids()
+            .filter(|state| is_reachable[state.0] && is_alive[state.0])
+            .map(|state| self.node(state))
+            .flat_map(|node| node.transitions.iter().map(move |edge| (node.id.as_str(), edge)))
+            .map(|(source, edge)| (source, self.node(edge.destination).id.as_str(), edge.symbols.clone()))
+            .filter(|(_, destination, _)| kept.contains(destination))
+            .collect();
+
+        let fa = FiniteAutomaton::with_symbol_sets(states,
+                                                   self.node(self.start).id.as_str(),
+                                                   final_states,
+                                                   self.alphabet.clone(),
+                                                   transitions);
+        (fa, report)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::finite_automata::equivalence::Equivalence;
+    use crate::finite_automata::fa::EPSILON;
+
+    #[test]
+    fn test_trim() {
+        let fa = FiniteAutomaton::new(
+            vec!["p", "q", "r", "dead", "unreachable"],
+            "p",
+            HashSet::from(["r", "unreachable"]),
+            HashSet::from(['a', 'b']),
+            vec![("p", "q", EPSILON), ("q", "r", 'a'), ("q", "dead", 'b'), ("dead", "dead", 'a'),
+                 ("unreachable", "p", 'a'), ("r", "r", 'b')]
+        );
+
+        let (trimmed, report) = fa.trim();
+        assert_eq!(report, TrimReport { unreachable: vec!["unreachable".to_owned()], dead: vec!["dead".to_owned()] });
+        assert_eq!(trimmed.nodes.len(), 3);
+        assert_eq!(trimmed.transitions().len(), 3);
+        assert_eq!(trimmed.equivalent(&fa), Equivalence::Equivalent);
+
+        let (again, report) = trimmed.trim();
+        assert!(report.is_empty());
+        assert_eq!(again, trimmed);
+    }
+
+    #[test]
+    fn test_trim_empty_language() {
+        let fa = FiniteAutomaton::new(vec!["p", "q"], "p", HashSet::new(), HashSet::from(['a', 'b']),
+                                      vec![("p", "q", 'a'), ("p", "p", 'b'), ("q", "p", 'a')]);
+        let (trimmed, report) = fa.trim();
+        assert_eq!(report.dead, vec!["q".to_owned()]);
+        assert_eq!(trimmed.nodes.len(), 1);
+        assert!(trimmed.node(trimmed.start).transitions.is_empty());
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use crate::finite_automata::fa::{Edge, EPSILON, FiniteAutomaton};
+use crate::finite_automata::symbol_set::SymbolSet;
+
+/// Characters that cannot be part of a state name in the five-line format.
+const STATE_SPECIAL_CHARS: &str = ",;\\\n\r";
+
+#[derive(Debug)]
+pub enum WriteError {
+    Io(std::io::Error),
+    InvalidStateName { state: String },
+    DuplicateState { state: String }
+}
+
+impl Display for WriteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WriteError::Io(err) => write!(f, "could not write the file: {}", err),
+            WriteError::InvalidStateName { state } => write!(f, "state \"{}\" cannot be written to an FA file", state),
+            WriteError::DuplicateState { state } => write!(f, "state {} appears twice", state)
+        }
+    }
+}
+
+impl std::error::Error for WriteError {}
+
+fn check_state_name(state: &str) -> Result<(), WriteError> {
+    if state.is_empty() || state.trim() != state || state.contains(|ch| STATE_SPECIAL_CHARS.contains(ch)) {
+        return Err(WriteError::InvalidStateName { state: state.to_owned() });
+    }
+
+    Ok(())
+}
+
+fn label(edge: &Edge) -> String {
+    if edge.is_epsilon() {
+        EPSILON.to_string()
+    } else {
+        edge.symbols.to_string()
+    }
+}
+
+impl FiniteAutomaton {
+    /// Writes `self` in the five-line format described in `fa_file_ebnf`, so that `parse_fa_str`
+    /// reads back the same automaton. Symbols are grouped into ranges where possible.
+    pub fn to_fa_string(&self) -> Result<String, WriteError> {
+        let mut seen = HashSet::new();
+        for node in &self.nodes {
+            check_state_name(&node.id)?;
+            if !seen.insert(node.id.as_str()) {
+                return Err(WriteError::DuplicateState { state: node.id.clone() });
+            }
+        }
+
+        let states: Vec<&str> = self.nodes.iter().map(|node| node.id.as_str()).collect();
+        let final_states: Vec<&str> = self.nodes.iter()
+            .filter(|node| node.is_final_state)
+            .map(|node| node.id.as_str())
+            .collect();
+
+        let alphabet: SymbolSet = self.alphabet.iter().cloned().collect();
+        let alphabet: Vec<String> = alphabet.ranges()
+            .iter()
+            .map(|range| SymbolSet::from_ranges([*range]).to_string())
+            .collect();
+
+        let transitions: Vec<String> = self.nodes.iter()
+            .flat_map(|node| node.transitions.iter().map(move |edge| (node, edge)))
+            .map(|(node, edge)| format!("{},{},{}", node.id, self.node(edge.destination).id, label(edge)))
+            .collect();
+
+        Ok(format!("{}\n{}\n{}\n{}\n{}\n",
+                   states.join(","),
+                   self.node(self.start).id,
+                   final_states.join(","),
+                   alphabet.join(","),
+                   transitions.join(";")))
+    }
+}
+
+/// Saves `fa` to `file_name` in the format read by `parse_fa`: JSON if the name ends in `.json`,
+/// the five-line format otherwise.
+pub fn write_fa(fa: &FiniteAutomaton, file_name: &str) -> Result<(), WriteError> {
+    let content = if file_name.ends_with(".json") {
+        fa.to_json()
+    } else {
+        fa.to_fa_string()?
+    };
+
+    fs::write(file_name, content).map_err(WriteError::Io)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use proptest::prelude::*;
+    use super::*;
+    use crate::finite_automata::parser::parse_fa_str;
+
+    const STATE_NAMES: [&str; 6] = ["p", "q10", "{p|q}", "(p|∅)", "s 1", "0"];
+    const SYMBOLS: [char; 12] = ['a', 'b', 'c', '0', '-', ',', ';', '\\', ' ', '[', ']', '^'];
+
+    prop_compose! {
+        fn automaton()(state_count in 1..=STATE_NAMES.len())
+                      (finals in proptest::collection::vec(any::<bool>(), state_count),
+                       start in 0..state_count,
+                       alphabet in proptest::sample::subsequence(SYMBOLS.to_vec(), 1..=SYMBOLS.len()),
+                       edges in proptest::collection::vec((0..state_count, 0..state_count, any::<proptest::sample::Index>(), any::<bool>()), 0..12),
+                       state_count in Just(state_count))
+                      -> FiniteAutomaton {
+            let states = STATE_NAMES[..state_count].to_vec();
+            let final_states = states.iter().zip(&finals).filter(|(_, is_final)| **is_final).map(|(state, _)| *state).collect();
+            let transitions = edges.iter()
+                .map(|(source, destination, symbol, is_epsilon)| {
+                    let symbol = if *is_epsilon { EPSILON } else { *symbol.get(&alphabet) };
+                    (states[*source], states[*destination], symbol)
+                })
+                .collect();
+
+            FiniteAutomaton::new(states.clone(), states[start], final_states, alphabet.iter().cloned().collect(), transitions)
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip(fa in automaton()) {
+            let written = fa.to_fa_string().unwrap();
+            let parsed = parse_fa_str(&written).unwrap();
+            prop_assert_eq!(parsed.to_fa_string().unwrap(), written);
+            prop_assert_eq!(parsed.transitions(), fa.transitions());
+            prop_assert_eq!(parsed.alphabet, fa.alphabet);
+        }
+    }
+
+    #[test]
+    fn test_to_fa_string() {
+        let fa = parse_fa_str("p,z,s,n\np\nz,n\n0-9,+,-\np,z,0;p,s,[+\\-];s,n,1-9;p,n,1-9;n,n,[:digit:]").unwrap();
+        assert_eq!(fa.to_fa_string().unwrap(), "p,z,s,n\np\nz,n\n+,-,0-9\np,z,0;p,s,[+\\-];p,n,1-9;s,n,1-9;n,n,0-9\n");
+
+        let dfa = fa.minimize().0;
+        assert!(parse_fa_str(&dfa.to_fa_string().unwrap()).unwrap().check_match("-42").unwrap());
+
+        let fa = FiniteAutomaton::new(vec!["p,q"], "p,q", HashSet::new(), HashSet::new(), Vec::new());
+        assert!(matches!(fa.to_fa_string(), Err(WriteError::InvalidStateName { .. })));
+    }
+}
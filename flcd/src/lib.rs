@@ -0,0 +1,5 @@
+pub mod symbol_table;
+pub mod token;
+pub mod scanner;
+pub mod pif;
+pub mod finite_automata;
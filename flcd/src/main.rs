@@ -1,18 +1,39 @@
 use std::io;
-use crate::scanner::source_code_scanner::process_source_code;
-use crate::token::token_manager::TokenManager;
+use flcd::finite_automata;
+use flcd::finite_automata::fa::FiniteAutomaton;
+use flcd::finite_automata::parser::parse_fa;
+use flcd::scanner::source_code_scanner::process_source_code;
+use flcd::token::token_manager::TokenManager;
 
-mod symbol_table;
-mod token;
-mod scanner;
-mod pif;
-mod finite_automata;
+fn read_token_fa(file_name: &str) -> Option<FiniteAutomaton> {
+    match parse_fa(file_name) {
+        Ok(fa) => Some(fa.minimize().0),
+        Err(err) => {
+            println!("{}: {}", file_name, err);
+            None
+        }
+    }
+}
 
 fn parse_source_code() {
     let token_manager = TokenManager::new("token.in");
-    let int_const_fa = finite_automata::parser::parse_fa("int.in");
-    let id_fa = finite_automata::parser::parse_fa("id.in");
-    process_source_code("test.txt", &token_manager, &int_const_fa, &id_fa);
+    let Some(int_const_fa) = read_token_fa("int.in") else { return };
+    // `.` in string.re only covers the Basic Multilingual Plane, so string constants with
+    // characters beyond it, like emoji, are lexical errors.
+    let Some(string_const_fa) = read_token_fa("string.re") else { return };
+    let Some(id_fa) = read_token_fa("id.in") else { return };
+
+    let (overlap, _) = int_const_fa.intersection(&id_fa).minimize();
+    if overlap.nodes.iter().any(|node| node.is_final_state) {
+        println!("Warning: int.in and id.in accept some of the same tokens");
+    }
+
+    let compile = |fa: &FiniteAutomaton| fa.compile().expect("minimized automata are deterministic");
+    process_source_code("test.txt",
+                        &token_manager,
+                        &compile(&int_const_fa),
+                        &compile(&string_const_fa),
+                        &compile(&id_fa));
 }
 
 fn main() {
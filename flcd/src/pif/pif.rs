@@ -14,6 +14,7 @@ impl fmt::Display for PIFEntry {
     }
 }
 
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug)]
 pub struct PIF {
     pif: Vec<PIFEntry>
@@ -31,6 +32,12 @@ impl PIF {
     }
 }
 
+impl Default for PIF {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl fmt::Display for PIF {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let mut res = String::new();
@@ -1,2 +1,2 @@
-pub(crate) mod source_code_scanner;
+pub mod source_code_scanner;
 mod char_type;
\ No newline at end of file
@@ -1,7 +1,6 @@
 use std::fs::File;
 use std::io::{self, BufRead};
-use regex::Regex;
-use crate::finite_automata::fa::FiniteAutomaton;
+use crate::finite_automata::compiled::CompiledDfa;
 use crate::pif::pif::{PIF, PIFEntry};
 use crate::scanner::char_type::CharType;
 use crate::symbol_table::symbol_table::{SymbolTable, SymbolTableType};
@@ -59,26 +58,8 @@ fn extract_tokens_from_line(line: &str) -> Vec<String> {
     rez
 }
 
-fn is_int_constant(token: &str) -> bool {
-    if token == "0" {
-        return true;
-    }
-    let re = Regex::new(r"^-?[1-9]\d*$").unwrap();
-    re.is_match(token)
-}
-
-fn is_string_constant(token: &str) -> bool {
-    let re = Regex::new(r#"^".*"$"#).unwrap();
-    re.is_match(token)
-}
-
-fn is_id(token: &str) -> bool {
-    let re = Regex::new(r"^[a-zA-Z_]+$").unwrap();
-    re.is_match(token)
-}
-
-fn add_id_or_constant_to_pif(pif: &mut PIF, token: &str, symbol_table: &mut SymbolTable, line_num: i32, int_const_fa: &FiniteAutomaton, id_fa: &FiniteAutomaton) {
-    let st_pos = if int_const_fa.check_match(token).unwrap() {
+fn add_id_or_constant_to_pif(pif: &mut PIF, token: &str, symbol_table: &mut SymbolTable, line_num: i32, int_const_fa: &CompiledDfa, string_const_fa: &CompiledDfa, id_fa: &CompiledDfa) {
+    let st_pos = if int_const_fa.is_match(token) {
         let pos = symbol_table.add_int_const(token.parse().unwrap());
         SymbolTablePosition {
             tp: SymbolTableType::INT,
@@ -86,7 +67,7 @@ fn add_id_or_constant_to_pif(pif: &mut PIF, token: &str, symbol_table: &mut Symb
             item: pos.1,
         }
     }
-    else if is_string_constant(token) {
+    else if string_const_fa.is_match(token) {
         let pos = symbol_table.add_string_const(token.to_owned());
         SymbolTablePosition {
             tp: SymbolTableType::STRING,
@@ -94,7 +75,7 @@ fn add_id_or_constant_to_pif(pif: &mut PIF, token: &str, symbol_table: &mut Symb
             item: pos.1,
         }
     }
-    else if id_fa.check_match(token).unwrap() {
+    else if id_fa.is_match(token) {
         let pos = symbol_table.add_id(token.to_owned());
         SymbolTablePosition {
             tp: SymbolTableType::ID,
@@ -112,19 +93,19 @@ fn add_id_or_constant_to_pif(pif: &mut PIF, token: &str, symbol_table: &mut Symb
     });
 }
 
-pub fn process_source_code(file_name: &str, token_manager: &TokenManager, int_const_fa: &FiniteAutomaton, id_fa: &FiniteAutomaton) {
+pub fn process_source_code(file_name: &str, token_manager: &TokenManager, int_const_fa: &CompiledDfa, string_const_fa: &CompiledDfa, id_fa: &CompiledDfa) {
     let mut res = PIF::new();
     let mut symbol_table = SymbolTable::new(10);
 
     if let Ok(lines) = scan_source_code(file_name) {
-        let mut line_num = 1;
-        for line in lines {
+        for (line_idx, line) in lines.enumerate() {
+            let line_num = line_idx as i32 + 1;
             if let Ok(ip) = line {
                 let tokens = extract_tokens_from_line(&ip);
 
                 for token in tokens {
                     match token_manager.get_token_code(&token) {
-                        None => add_id_or_constant_to_pif(&mut res, &token, &mut symbol_table, line_num, int_const_fa, id_fa),
+                        None => add_id_or_constant_to_pif(&mut res, &token, &mut symbol_table, line_num, int_const_fa, string_const_fa, id_fa),
                         Some(code) =>
                             res.add(PIFEntry{
                                 token: code,
@@ -133,7 +114,6 @@ pub fn process_source_code(file_name: &str, token_manager: &TokenManager, int_co
                     }
                 }
             }
-            line_num += 1;
         }
     }
 
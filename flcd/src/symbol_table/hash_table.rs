@@ -13,10 +13,8 @@ pub struct MyHashMap<Key> {
 impl<Key: Eq + Hash + Clone + Debug> fmt::Display for MyHashMap<Key> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let mut res = String::new();
-        let mut bucket = 0;
-        for el in &self.table {
+        for (bucket, el) in self.table.iter().enumerate() {
             res.push_str(&format!("Bucket {}: {:?}\n", bucket, el));
-            bucket += 1;
         }
 
         write!(f, "{}", res)
@@ -42,9 +40,10 @@ impl<Key: Eq + Hash + Clone> MyHashMap<Key> {
     {
         let mut hasher = DefaultHasher::new();
         value.hash(&mut hasher);
-        return (hasher.finish() % (self.size as u64)) as usize;
+        (hasher.finish() % (self.size as u64)) as usize
     }
 
+    #[allow(clippy::result_unit_err)]
     pub fn insert(&mut self, key: Key) -> Result<(usize, usize), ()>
     {
         if self.get(&key).is_some() {
@@ -63,14 +62,11 @@ impl<Key: Eq + Hash + Clone> MyHashMap<Key> {
         where Key: Borrow<Q>,
               Q: Hash + Eq + ?Sized
     {
-        let hash = self.get_hash(&key);
+        let hash = self.get_hash(key);
 
-        let list_opt = self.table.get(hash);
-        if list_opt.is_none() {
-            return None;
-        }
+        let list = self.table.get(hash)?;
 
-        list_opt.unwrap().iter().position(|el| el.borrow() == key).map(|el| (hash, el))
+        list.iter().position(|el| el.borrow() == key).map(|el| (hash, el))
     }
 
     pub fn get_by_position(&self, position: &(usize, usize)) -> Option<&Key> {
@@ -80,6 +76,7 @@ impl<Key: Eq + Hash + Clone> MyHashMap<Key> {
         }
     }
 
+    #[allow(clippy::result_unit_err)]
     pub fn delete<Q>(&mut self, key: &Q) -> Result<(), ()>
         where Key: Borrow<Q>,
               Q: Hash + Eq + ?Sized
@@ -88,13 +85,13 @@ impl<Key: Eq + Hash + Clone> MyHashMap<Key> {
             return Err(());
         }
 
-        let hash = self.get_hash(&key);
+        let hash = self.get_hash(key);
 
         self.table.get_mut(hash)
             .expect("Hash function returned hash larger than size")
             .retain(|el| el.borrow() != key);
 
-        return Ok(())
+        Ok(())
     }
 }
 
@@ -130,7 +127,7 @@ mod test {
         let mut my_map = before_each();
 
         my_map.insert("d".to_owned()).expect("");
-        assert!(my_map.get("d".borrow()).is_some());
+        assert!(my_map.get("d").is_some());
         assert!(my_map.get("e").is_none());
     }
 
@@ -1,3 +1,4 @@
-mod hash_table;
-pub(crate) mod symbol_table;
-pub mod symbol_table_position;
\ No newline at end of file
+pub mod hash_table;
+#[allow(clippy::module_inception)]
+pub mod symbol_table;
+pub mod symbol_table_position;
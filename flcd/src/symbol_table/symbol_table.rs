@@ -2,6 +2,7 @@ use std::fmt;
 use std::fmt::Formatter;
 use crate::symbol_table::hash_table::MyHashMap;
 
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug)]
 pub enum SymbolTableType {
     ID,
@@ -86,14 +87,13 @@ mod test {
     use super::*;
 
     fn before_each() -> SymbolTable {
-        let st = SymbolTable::new(16);
-        st
+        SymbolTable::new(16)
     }
 
     #[test]
     fn test_id() {
         let mut st = before_each();
-        st.add_id("a".to_owned()).unwrap();
+        st.add_id("a".to_owned());
 
         assert!(st.find_id("a").is_some());
         let pos = st.find_id("a").unwrap();
@@ -103,7 +103,7 @@ mod test {
     #[test]
     fn test_int() {
         let mut st = before_each();
-        st.add_string_const("a".to_owned()).unwrap();
+        st.add_string_const("a".to_owned());
 
         assert!(st.find_string("a").is_some());
         let pos = st.find_string("a").unwrap();
@@ -113,7 +113,7 @@ mod test {
     #[test]
     fn test_string() {
         let mut st = before_each();
-        st.add_int_const(1).unwrap();
+        st.add_int_const(1);
 
         assert!(st.find_int(&1).is_some());
         let pos = st.find_int(&1).unwrap();
@@ -1,2 +1,2 @@
 mod token_scanner;
-pub(crate) mod token_manager;
\ No newline at end of file
+pub mod token_manager;
\ No newline at end of file
@@ -10,10 +10,8 @@ impl TokenManager {
         let lines = token_scanner::scan_token_file(file_name).unwrap();
 
         let mut hash_table = HashMap::new();
-        let mut idx = 0;
-        for line in lines {
-            hash_table.insert(line, idx);
-            idx += 1;
+        for (idx, line) in lines.into_iter().enumerate() {
+            hash_table.insert(line, idx as isize);
         }
 
         Self {